@@ -0,0 +1,130 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{Clock, NANOS_PER_DAY};
+
+// where the scheduler reads the current time from
+pub trait TimeSource {
+    fn now(&self) -> Clock;
+}
+
+// wall clock time, in UTC
+pub struct SystemTimeSource;
+
+impl TimeSource for SystemTimeSource {
+    fn now(&self) -> Clock {
+        let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        Clock::new(0, 0) + since_epoch
+    }
+}
+
+// manually driven time for tests; clones share the same time, so a test can keep a
+// handle and advance it while the scheduler owns another one
+#[derive(Debug, Clone)]
+pub struct FakeTimeSource {
+    now: Arc<Mutex<Clock>>,
+}
+
+impl FakeTimeSource {
+    pub fn new(start: Clock) -> Self {
+        FakeTimeSource { now: Arc::new(Mutex::new(start)) }
+    }
+
+    pub fn set(&self, c: Clock) {
+        *self.now.lock().unwrap() = c;
+    }
+
+    pub fn advance(&self, d: Duration) {
+        *self.now.lock().unwrap() += d;
+    }
+}
+
+impl TimeSource for FakeTimeSource {
+    fn now(&self) -> Clock {
+        *self.now.lock().unwrap()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AlarmId(usize);
+
+struct Alarm {
+    id: AlarmId,
+    at: Clock,
+    repeat: bool,
+    callback: Box<dyn FnMut(Clock)>,
+}
+
+pub struct AlarmScheduler<T: TimeSource> {
+    source: T,
+    alarms: Vec<Alarm>,
+    // time of the previous tick, alarms fire when their time is in (last, now]
+    last: Clock,
+    next_id: usize,
+}
+
+impl<T: TimeSource> AlarmScheduler<T> {
+    pub fn new(source: T) -> Self {
+        let last = source.now();
+        AlarmScheduler { source, alarms: vec![], last, next_id: 0 }
+    }
+
+    fn push(&mut self, at: Clock, repeat: bool, callback: Box<dyn FnMut(Clock)>) -> AlarmId {
+        let id = AlarmId(self.next_id);
+        self.next_id += 1;
+        self.alarms.push(Alarm { id, at, repeat, callback });
+        id
+    }
+
+    // fires every day at the given time
+    pub fn add(&mut self, at: Clock, callback: impl FnMut(Clock) + 'static) -> AlarmId {
+        self.push(at, true, Box::new(callback))
+    }
+
+    // fires only the first time at is reached, then it's removed
+    pub fn add_once(&mut self, at: Clock, callback: impl FnMut(Clock) + 'static) -> AlarmId {
+        self.push(at, false, Box::new(callback))
+    }
+
+    // false if there was no such alarm
+    pub fn cancel(&mut self, id: AlarmId) -> bool {
+        let len = self.alarms.len();
+        self.alarms.retain(|a| a.id != id);
+        self.alarms.len() != len
+    }
+
+    pub fn pending(&self) -> usize {
+        self.alarms.len()
+    }
+
+    // reads the time source and fires, in chronological order, the alarms reached since the previous tick.
+    // A clock has no date, so tick() must be called at least once a day or days are lost
+    pub fn tick(&mut self) -> Vec<AlarmId> {
+        let now = self.source.now();
+        let last = self.last;
+        let elapsed = (now.nanos - last.nanos).rem_euclid(NANOS_PER_DAY);
+        self.last = now;
+        if elapsed == 0 {
+            return vec![];
+        }
+
+        let offset = |at: Clock| (at.nanos - last.nanos).rem_euclid(NANOS_PER_DAY);
+        let mut due: Vec<(i64, usize)> = self
+            .alarms
+            .iter()
+            .enumerate()
+            .map(|(i, a)| (offset(a.at), i))
+            .filter(|&(o, _)| o > 0 && o <= elapsed)
+            .collect();
+        due.sort();
+
+        let mut fired = vec![];
+        for &(_, i) in due.iter() {
+            let alarm = &mut self.alarms[i];
+            (alarm.callback)(alarm.at);
+            fired.push(alarm.id);
+        }
+        self.alarms.retain(|a| a.repeat || !fired.contains(&a.id));
+        fired
+    }
+}
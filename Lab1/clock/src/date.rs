@@ -0,0 +1,208 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::time::Duration;
+
+use crate::{Clock, TimeDelta};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<usize> for Weekday {
+    fn from(d: usize) -> Weekday {
+        match d % 7 {
+            0 => Weekday::Monday,
+            1 => Weekday::Tuesday,
+            2 => Weekday::Wednesday,
+            3 => Weekday::Thursday,
+            4 => Weekday::Friday,
+            5 => Weekday::Saturday,
+            6 => Weekday::Sunday,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Weekday {
+    pub fn succ(&self) -> Weekday {
+        Weekday::from(*self as usize + 1)
+    }
+
+    pub fn is_weekend(&self) -> bool {
+        matches!(self, Weekday::Saturday | Weekday::Sunday)
+    }
+}
+
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+// proleptic gregorian calendar date; fields are ordered so that the derived Ord is chronological
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    // None if the date doesn't exist (e.g. 2023-02-29)
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Date { year, month, day })
+    }
+
+    // as for Clock, we convert to a simple unit (days since 1970-01-01) to do arithmetic
+    // see http://howardhinnant.github.io/date_algorithms.html
+    fn to_days(self) -> i64 {
+        let y = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let mp = (self.month as i64 + 9) % 12; // march is month 0
+        let doy = (153 * mp + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146097 + doe - 719468
+    }
+
+    fn from_days(days: i64) -> Self {
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        Date { year, month, day }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    pub fn add_days(&self, days: i64) -> Self {
+        Date::from_days(self.to_days() + days)
+    }
+
+    // signed number of days from other to self
+    pub fn days_since(&self, other: Date) -> i64 {
+        self.to_days() - other.to_days()
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        // 1970-01-01 was a thursday
+        Weekday::from((self.to_days() + 3).rem_euclid(7) as usize)
+    }
+
+    pub fn ordinal(&self) -> u32 {
+        self.days_since(Date { year: self.year, month: 1, day: 1 }) as u32 + 1
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+// a Clock on a given Date: unlike Clock, adding time never loses the elapsed days
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    date: Date,
+    time: Clock,
+}
+
+impl DateTime {
+    pub fn new(date: Date, time: Clock) -> Self {
+        DateTime { date, time }
+    }
+
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    pub fn time(&self) -> Clock {
+        self.time
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        self.date.weekday()
+    }
+
+    pub fn add_minutes(&self, minutes: i32) -> Self {
+        let (time, days) = self.time.add_minutes_with_carry(minutes);
+        DateTime { date: self.date.add_days(days as i64), time }
+    }
+
+    pub fn add_seconds(&self, seconds: i64) -> Self {
+        let (time, days) = self.time.add_nanos_with_carry(seconds as i128 * 1_000_000_000);
+        DateTime { date: self.date.add_days(days), time }
+    }
+}
+
+// ISO-8601, with the seconds always and the fraction of second only when there is one
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pattern = if self.time.nanoseconds() == 0 { "%H:%M:%S" } else { "%H:%M:%S.%f" };
+        write!(f, "{}T{}", self.date, self.time.format(pattern))
+    }
+}
+
+impl Add<Duration> for DateTime {
+    type Output = DateTime;
+
+    fn add(self, rhs: Duration) -> DateTime {
+        let (time, days) = self.time.add_nanos_with_carry(rhs.as_nanos() as i128);
+        DateTime { date: self.date.add_days(days), time }
+    }
+}
+
+impl Sub<Duration> for DateTime {
+    type Output = DateTime;
+
+    fn sub(self, rhs: Duration) -> DateTime {
+        let (time, days) = self.time.add_nanos_with_carry(-(rhs.as_nanos() as i128));
+        DateTime { date: self.date.add_days(days), time }
+    }
+}
+
+// signed distance from rhs to self; unlike Clock there is no wrap-around
+impl Sub<DateTime> for DateTime {
+    type Output = TimeDelta;
+
+    fn sub(self, rhs: DateTime) -> TimeDelta {
+        let days = self.date.days_since(rhs.date) as i128;
+        TimeDelta::from_nanos(
+            days * 24 * 3600 * 1_000_000_000 + self.time.since_midnight().as_nanos() as i128
+                - rhs.time.since_midnight().as_nanos() as i128,
+        )
+    }
+}
@@ -0,0 +1,93 @@
+use crate::parse::{hour_from_12, ClockParseError, Cursor};
+use crate::Clock;
+
+// strftime-like specifiers, a '-' after '%' removes the zero padding (e.g. %-I):
+//   %H  hour 00-23        %I  hour 01-12       %M  minute 00-59
+//   %S  second 00-59      %f  nanoseconds      %p  AM/PM        %P  am/pm
+//   %%  a literal '%'
+// everything else is copied as is
+impl Clock {
+    pub fn format(&self, pattern: &str) -> String {
+        let mut out = String::new();
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            let pad = chars.next_if_eq(&'-').is_none();
+            let num = |n: i32| if pad { format!("{:02}", n) } else { n.to_string() };
+            match chars.next() {
+                Some('H') => out.push_str(&num(self.hours())),
+                Some('I') => out.push_str(&num((self.hours() + 11) % 12 + 1)),
+                Some('M') => out.push_str(&num(self.minutes())),
+                Some('S') => out.push_str(&num(self.seconds())),
+                Some('f') => out.push_str(&format!("{:09}", self.nanoseconds())),
+                Some('p') => out.push_str(if self.hours() < 12 { "AM" } else { "PM" }),
+                Some('P') => out.push_str(if self.hours() < 12 { "am" } else { "pm" }),
+                Some('%') => out.push('%'),
+                // unknown specifier: keep it verbatim
+                Some(x) => {
+                    out.push('%');
+                    if !pad {
+                        out.push('-');
+                    }
+                    out.push(x);
+                }
+                None => out.push('%'),
+            }
+        }
+        out
+    }
+
+    // inverse of format(): the input must match the pattern exactly; a '-' flag accepts one or two digits.
+    // %p/%P only matter with %I, and with %I alone the time is taken as am
+    pub fn parse_with(pattern: &str, input: &str) -> Result<Clock, ClockParseError> {
+        let mut c = Cursor::new(input);
+        let (mut hours, mut minutes, mut seconds, mut nanos) = (0, 0, 0, 0);
+        let mut twelve = false;
+        let mut pm = false;
+
+        let mut p = pattern.chars().enumerate().peekable();
+        while let Some((i, pc)) = p.next() {
+            if pc != '%' {
+                c.expect(pc, "pattern literal")?;
+                continue;
+            }
+            let min = if p.next_if(|&(_, x)| x == '-').is_some() { 1 } else { 2 };
+            match p.next().map(|(_, x)| x) {
+                Some('H') => hours = c.number(min, 2, "hour", 0..=23)?,
+                Some('I') => {
+                    hours = c.number(min, 2, "hour", 1..=12)?;
+                    twelve = true;
+                }
+                Some('M') => minutes = c.number(min, 2, "minute", 0..=59)?,
+                Some('S') => seconds = c.number(min, 2, "second", 0..=59)?,
+                Some('f') => {
+                    let (value, n) = c.digits(1, 9, "fraction of second")?;
+                    nanos = value * 10u32.pow(9 - n as u32);
+                }
+                Some('p') | Some('P') => {
+                    let pos = c.pos();
+                    match c.meridiem()? {
+                        Some(x) => pm = x,
+                        None => {
+                            return Err(match c.peek() {
+                                Some(found) => ClockParseError::UnexpectedChar { pos, found, expected: "am/pm" },
+                                None => ClockParseError::UnexpectedEnd { pos, expected: "am/pm" },
+                            })
+                        }
+                    }
+                }
+                Some('%') => c.expect('%', "'%'")?,
+                _ => return Err(ClockParseError::InvalidPattern { pos: i }),
+            }
+        }
+        c.end()?;
+
+        if twelve {
+            hours = hour_from_12(hours, pm);
+        }
+        Ok(Clock::from_hms_nano(hours as i32, minutes as i32, seconds as i32, nanos as i64))
+    }
+}
@@ -1,43 +1,188 @@
 use  std::fmt;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::time::Duration;
 
-#[derive(Debug, PartialEq)]
+mod alarm;
+mod date;
+mod format;
+mod parse;
+mod range;
+mod schedule;
+
+pub use alarm::{AlarmId, AlarmScheduler, FakeTimeSource, SystemTimeSource, TimeSource};
+pub use date::{days_in_month, is_leap_year, Date, DateTime, Weekday};
+pub use parse::ClockParseError;
+pub use range::{Steps, TimeRange};
+pub use schedule::{DateTimeScheduleIter, Schedule, ScheduleError, ScheduleIter};
+
+const NANOS_PER_SEC: i64 = 1_000_000_000;
+const NANOS_PER_MIN: i64 = 60 * NANOS_PER_SEC;
+const NANOS_PER_HOUR: i64 = 60 * NANOS_PER_MIN;
+const NANOS_PER_DAY: i64 = 24 * NANOS_PER_HOUR;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Clock {
-    // internally we store the time in minutes, so that we can easily perform arithmetic operations
+    // internally we store the time in nanoseconds since midnight, so that we can easily perform arithmetic operations
     // please note that all date/time libraries use this approach, storing time in simple units
-    minutes: i32
+    nanos: i64
 }
 
 impl Clock {
-    
-    // adjusting sign of minutes to be positive
-    fn from_minutes(minutes: i32) -> Self {
-        if minutes < 0 {
-            // we need to add 24 hours to the negative minutes
-            Clock { minutes: (minutes % (60*24)) + (60*24) }
-        } else {
-            Clock { minutes: minutes % (60*24) }
-        }
+
+    // adjusting sign of nanoseconds to be positive, always in 0..NANOS_PER_DAY
+    fn from_nanos(nanos: i64) -> Self {
+        Clock { nanos: nanos.rem_euclid(NANOS_PER_DAY) }
+    }
+
+    fn from_minutes(minutes: i64) -> Self {
+        Clock::from_nanos((minutes % (60*24)) * NANOS_PER_MIN)
     }
 
     pub fn new(hours: i32, minutes: i32) -> Self {
-        Clock::from_minutes(hours * 60 + minutes)
+        Clock::from_minutes(hours as i64 * 60 + minutes as i64)
+    }
+
+    // same rules as new(): every field may overflow or be negative, the result is normalised
+    pub fn from_hms(hours: i32, minutes: i32, seconds: i32) -> Self {
+        Clock::from_hms_nano(hours, minutes, seconds, 0)
+    }
+
+    pub fn from_hms_nano(hours: i32, minutes: i32, seconds: i32, nanos: i64) -> Self {
+        let secs = (hours as i64 * 60 + minutes as i64) * 60 + seconds as i64;
+        Clock::from_nanos((secs % (60*60*24)) * NANOS_PER_SEC + nanos % NANOS_PER_DAY)
     }
 
     pub fn add_minutes(&self, minutes: i32) -> Self {
-        // with internal time in minutes it's trivial to add minutes
-        Clock::from_minutes(self.minutes + minutes)
+        // with internal time in simple units it's trivial to add minutes
+        Clock::from_nanos(self.nanos + Clock::from_minutes(minutes as i64).nanos)
     }
 
-    pub fn to_string(&self) -> String {
-        // we convert to hours and minutes only before display
-        let hours = self.minutes / 60;
-        let minutes = self.minutes % 60;
-        format!("{:02}:{:02}", hours, minutes)
+    pub fn add_seconds(&self, seconds: i64) -> Self {
+        Clock::from_nanos(self.nanos + (seconds % (60*60*24)) * NANOS_PER_SEC)
+    }
+
+    // same as add_minutes(), but also returns how many midnights were crossed (negative going backwards)
+    pub fn add_minutes_with_carry(&self, minutes: i32) -> (Self, i32) {
+        let (clock, days) = self.add_nanos_with_carry(minutes as i128 * NANOS_PER_MIN as i128);
+        (clock, days as i32)
+    }
+
+    // i128 because minutes or seconds converted to nanoseconds may not fit in an i64
+    pub(crate) fn add_nanos_with_carry(&self, nanos: i128) -> (Self, i64) {
+        let total = self.nanos as i128 + nanos;
+        let days = total.div_euclid(NANOS_PER_DAY as i128);
+        (Clock::from_nanos(total.rem_euclid(NANOS_PER_DAY as i128) as i64), days as i64)
+    }
+
+    pub fn hours(&self) -> i32 {
+        (self.nanos / NANOS_PER_HOUR) as i32
+    }
+
+    pub fn minutes(&self) -> i32 {
+        (self.nanos % NANOS_PER_HOUR / NANOS_PER_MIN) as i32
+    }
+
+    pub fn seconds(&self) -> i32 {
+        (self.nanos % NANOS_PER_MIN / NANOS_PER_SEC) as i32
+    }
+
+    pub fn nanoseconds(&self) -> u32 {
+        (self.nanos % NANOS_PER_SEC) as u32
+    }
+
+    // time elapsed since the previous midnight
+    pub fn since_midnight(&self) -> Duration {
+        Duration::from_nanos(self.nanos as u64)
     }
 }
 
 impl fmt::Display for Clock {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        // we convert to hours and minutes only before display
+        write!(f, "{}", self.format("%H:%M"))
+    }
+}
+
+// a Duration can be longer than a day: only the remainder matters on a clock face
+fn day_nanos(d: Duration) -> i64 {
+    (d.as_nanos() % NANOS_PER_DAY as u128) as i64
+}
+
+impl Add<Duration> for Clock {
+    type Output = Clock;
+
+    fn add(self, rhs: Duration) -> Clock {
+        Clock::from_nanos(self.nanos + day_nanos(rhs))
+    }
+}
+
+impl Sub<Duration> for Clock {
+    type Output = Clock;
+
+    fn sub(self, rhs: Duration) -> Clock {
+        Clock::from_nanos(self.nanos - day_nanos(rhs))
+    }
+}
+
+impl AddAssign<Duration> for Clock {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<Duration> for Clock {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+// a signed distance in time: how long, and whether it goes backwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeDelta {
+    negative: bool,
+    duration: Duration,
+}
+
+impl TimeDelta {
+    pub fn forward(duration: Duration) -> Self {
+        TimeDelta { negative: false, duration }
+    }
+
+    // a zero delta is never negative
+    pub fn backward(duration: Duration) -> Self {
+        TimeDelta { negative: !duration.is_zero(), duration }
+    }
+
+    fn from_nanos(nanos: i128) -> Self {
+        let duration = Duration::new((nanos.unsigned_abs() / NANOS_PER_SEC as u128) as u64, (nanos.unsigned_abs() % NANOS_PER_SEC as u128) as u32);
+        if nanos < 0 {
+            TimeDelta::backward(duration)
+        } else {
+            TimeDelta::forward(duration)
+        }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    // the length, without the direction
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+// signed shortest distance going from rhs to self, crossing midnight if shorter:
+// the result is in (-12h, +12h], e.g. 00:10 - 23:50 is +20 minutes, not -23h40m
+impl Sub<Clock> for Clock {
+    type Output = TimeDelta;
+
+    fn sub(self, rhs: Clock) -> TimeDelta {
+        let diff = (self.nanos - rhs.nanos).rem_euclid(NANOS_PER_DAY);
+        if diff > NANOS_PER_DAY / 2 {
+            TimeDelta::from_nanos((diff - NANOS_PER_DAY) as i128)
+        } else {
+            TimeDelta::from_nanos(diff as i128)
+        }
     }
 }
@@ -0,0 +1,246 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Clock;
+
+// every error carries the position (in chars) of the offending input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClockParseError {
+    Empty,
+    UnexpectedEnd { pos: usize, expected: &'static str },
+    UnexpectedChar { pos: usize, found: char, expected: &'static str },
+    OutOfRange { pos: usize, field: &'static str, value: u32 },
+    // unknown specifier in a parse_with() pattern, pos is in the pattern
+    InvalidPattern { pos: usize },
+}
+
+impl ClockParseError {
+    pub fn position(&self) -> usize {
+        match self {
+            ClockParseError::Empty => 0,
+            ClockParseError::UnexpectedEnd { pos, .. }
+            | ClockParseError::UnexpectedChar { pos, .. }
+            | ClockParseError::OutOfRange { pos, .. }
+            | ClockParseError::InvalidPattern { pos } => *pos,
+        }
+    }
+}
+
+impl fmt::Display for ClockParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClockParseError::Empty => write!(f, "empty input"),
+            ClockParseError::UnexpectedEnd { pos, expected } => {
+                write!(f, "unexpected end of input at {}, expected {}", pos, expected)
+            }
+            ClockParseError::UnexpectedChar { pos, found, expected } => {
+                write!(f, "unexpected '{}' at {}, expected {}", found, pos, expected)
+            }
+            ClockParseError::OutOfRange { pos, field, value } => {
+                write!(f, "{} {} out of range at {}", field, value, pos)
+            }
+            ClockParseError::InvalidPattern { pos } => write!(f, "invalid pattern specifier at {}", pos),
+        }
+    }
+}
+
+impl Error for ClockParseError {}
+
+// minimal char cursor, shared with the pattern based parser in format.rs
+pub(crate) struct Cursor {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Cursor {
+    pub(crate) fn new(s: &str) -> Self {
+        Cursor { chars: s.chars().collect(), pos: 0 }
+    }
+
+    pub(crate) fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    // consume c if it's the next char
+    pub(crate) fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    // consume c or fail
+    pub(crate) fn expect(&mut self, c: char, expected: &'static str) -> Result<(), ClockParseError> {
+        self.expect_if(|x| x == c, expected)
+    }
+
+    // consume c in either case or fail, for markers like am/pm and Z
+    pub(crate) fn expect_ignore_case(&mut self, c: char, expected: &'static str) -> Result<(), ClockParseError> {
+        self.expect_if(|x| x.eq_ignore_ascii_case(&c), expected)
+    }
+
+    fn expect_if(&mut self, ok: impl Fn(char) -> bool, expected: &'static str) -> Result<(), ClockParseError> {
+        match self.peek() {
+            Some(x) if ok(x) => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(found) => Err(ClockParseError::UnexpectedChar { pos: self.pos, found, expected }),
+            None => Err(ClockParseError::UnexpectedEnd { pos: self.pos, expected }),
+        }
+    }
+
+    pub(crate) fn skip_spaces(&mut self) {
+        while self.peek() == Some(' ') {
+            self.pos += 1;
+        }
+    }
+
+    // read between min and max digits; returns the value and the number of digits read
+    pub(crate) fn digits(&mut self, min: usize, max: usize, expected: &'static str) -> Result<(u32, usize), ClockParseError> {
+        let mut value = 0;
+        let mut n = 0;
+        while n < max {
+            match self.peek().and_then(|c| c.to_digit(10)) {
+                Some(d) => {
+                    value = value * 10 + d;
+                    self.pos += 1;
+                    n += 1;
+                }
+                None => break,
+            }
+        }
+        if n < min {
+            return Err(match self.peek() {
+                Some(found) => ClockParseError::UnexpectedChar { pos: self.pos, found, expected },
+                None => ClockParseError::UnexpectedEnd { pos: self.pos, expected },
+            });
+        }
+        Ok((value, n))
+    }
+
+    // like digits() but the value must also be in range
+    pub(crate) fn number(&mut self, min: usize, max: usize, field: &'static str, range: std::ops::RangeInclusive<u32>) -> Result<u32, ClockParseError> {
+        let start = self.pos;
+        let (value, _) = self.digits(min, max, field)?;
+        if !range.contains(&value) {
+            return Err(ClockParseError::OutOfRange { pos: start, field, value });
+        }
+        Ok(value)
+    }
+
+    // optional ".fff" fraction of a second, up to nanoseconds
+    pub(crate) fn fraction(&mut self) -> Result<u32, ClockParseError> {
+        if !self.eat('.') {
+            return Ok(0);
+        }
+        let (value, n) = self.digits(1, 9, "fraction of second")?;
+        Ok(value * 10u32.pow(9 - n as u32))
+    }
+
+    // optional "am"/"pm" marker, returns Some(true) for pm
+    pub(crate) fn meridiem(&mut self) -> Result<Option<bool>, ClockParseError> {
+        let pm = match self.peek() {
+            Some('a') | Some('A') => false,
+            Some('p') | Some('P') => true,
+            _ => return Ok(None),
+        };
+        self.pos += 1;
+        self.expect_ignore_case('m', "'m' of am/pm")?;
+        Ok(Some(pm))
+    }
+
+    pub(crate) fn end(&self) -> Result<(), ClockParseError> {
+        match self.peek() {
+            Some(found) => Err(ClockParseError::UnexpectedChar { pos: self.pos, found, expected: "end of input" }),
+            None => Ok(()),
+        }
+    }
+}
+
+// 12 hours clock to 24 hours clock: 12am is midnight, 12pm is noon
+pub(crate) fn hour_from_12(hour: u32, pm: bool) -> u32 {
+    hour % 12 + if pm { 12 } else { 0 }
+}
+
+// accepted formats:
+//   HH:MM, H:MM, HH:MM:SS[.fff]      24 hours clock
+//   h:mm am, h:mm:ss PM, h:mmpm      12 hours clock
+//   T14:30Z, T14:30:15+02:00         ISO-8601 time, converted to UTC
+fn parse_clock(s: &str) -> Result<Clock, ClockParseError> {
+    if s.is_empty() {
+        return Err(ClockParseError::Empty);
+    }
+    let mut c = Cursor::new(s);
+    let iso = c.eat('T');
+
+    let hour_pos = c.pos();
+    let hour_digits = if iso { 2 } else { 1 };
+    let hours = c.number(hour_digits, 2, "hour", 0..=23)?;
+    c.expect(':', "':'")?;
+    let minutes = c.number(2, 2, "minute", 0..=59)?;
+    let (seconds, nanos) = if c.eat(':') {
+        let s = c.number(2, 2, "second", 0..=59)?;
+        (s, c.fraction()?)
+    } else {
+        (0, 0)
+    };
+
+    let mut hours = hours as i32;
+    let mut minutes = minutes as i32;
+
+    if iso {
+        let offset_pos = c.pos();
+        match c.peek() {
+            Some('Z') | Some('z') => c.expect_ignore_case('Z', "'Z'")?,
+            Some(sign @ ('+' | '-')) => {
+                c.expect(sign, "offset sign")?;
+                let oh = c.number(2, 2, "offset hour", 0..=23)? as i32;
+                c.eat(':');
+                let om = c.number(2, 2, "offset minute", 0..=59)? as i32;
+                // local time = UTC + offset, so we go back by the offset
+                let sign = if sign == '+' { -1 } else { 1 };
+                hours += sign * oh;
+                minutes += sign * om;
+            }
+            Some(found) => {
+                return Err(ClockParseError::UnexpectedChar { pos: offset_pos, found, expected: "'Z' or offset" })
+            }
+            None => {}
+        }
+    } else {
+        c.skip_spaces();
+        if let Some(pm) = c.meridiem()? {
+            if !(1..=12).contains(&hours) {
+                return Err(ClockParseError::OutOfRange { pos: hour_pos, field: "hour", value: hours as u32 });
+            }
+            hours = hour_from_12(hours as u32, pm) as i32;
+        }
+    }
+    c.end()?;
+
+    Ok(Clock::from_hms_nano(hours, minutes, seconds as i32, nanos as i64))
+}
+
+impl FromStr for Clock {
+    type Err = ClockParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_clock(s)
+    }
+}
+
+impl TryFrom<&str> for Clock {
+    type Error = ClockParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        parse_clock(s)
+    }
+}
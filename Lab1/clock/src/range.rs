@@ -0,0 +1,153 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::{Clock, NANOS_PER_DAY};
+
+// half-open interval [start, end) on the clock face; when end < start the range wraps past midnight,
+// e.g. 22:00-06:00 is a night shift of 8 hours
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeRange {
+    // as for Clock we store simple units: the start and the length in nanoseconds (0..=one day)
+    start: Clock,
+    len: i64,
+}
+
+impl TimeRange {
+    // start == end is an empty range, use full_day() for the whole day
+    pub fn new(start: Clock, end: Clock) -> Self {
+        TimeRange { start, len: (end.nanos - start.nanos).rem_euclid(NANOS_PER_DAY) }
+    }
+
+    // durations longer than a day are capped to the whole day
+    pub fn from_duration(start: Clock, duration: Duration) -> Self {
+        let len = duration.as_nanos().min(NANOS_PER_DAY as u128) as i64;
+        TimeRange { start, len }
+    }
+
+    pub fn full_day(start: Clock) -> Self {
+        TimeRange { start, len: NANOS_PER_DAY }
+    }
+
+    pub fn start(&self) -> Clock {
+        self.start
+    }
+
+    pub fn end(&self) -> Clock {
+        Clock::from_nanos(self.start.nanos + self.len)
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(self.len as u64)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn wraps_midnight(&self) -> bool {
+        self.start.nanos + self.len > NANOS_PER_DAY
+    }
+
+    // distance of c from our start going forward
+    fn offset(&self, c: Clock) -> i64 {
+        (c.nanos - self.start.nanos).rem_euclid(NANOS_PER_DAY)
+    }
+
+    pub fn contains(&self, c: Clock) -> bool {
+        self.offset(c) < self.len
+    }
+
+    pub fn overlaps(&self, other: &TimeRange) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    // split into at most two linear pieces inside 0..NANOS_PER_DAY
+    fn pieces(&self) -> Vec<(i64, i64)> {
+        let s = self.start.nanos;
+        let e = s + self.len;
+        if self.len == 0 {
+            vec![]
+        } else if e <= NANOS_PER_DAY {
+            vec![(s, e)]
+        } else {
+            vec![(s, NANOS_PER_DAY), (0, e - NANOS_PER_DAY)]
+        }
+    }
+
+    // two ranges wrapping past midnight may intersect in two separate pieces,
+    // e.g. 22:00-06:00 and 05:00-23:00 share 05:00-06:00 and 22:00-23:00
+    pub fn intersection(&self, other: &TimeRange) -> Vec<TimeRange> {
+        let mut linear = vec![];
+        for (s1, e1) in self.pieces() {
+            for (s2, e2) in other.pieces() {
+                let (s, e) = (s1.max(s2), e1.min(e2));
+                if s < e {
+                    linear.push((s, e));
+                }
+            }
+        }
+        linear.sort();
+
+        // a piece ending at midnight and one starting at midnight are the same range
+        if linear.len() > 1 && linear[0].0 == 0 && linear[linear.len() - 1].1 == NANOS_PER_DAY {
+            let (_, e) = linear.remove(0);
+            let last = linear.len() - 1;
+            linear[last].1 = NANOS_PER_DAY + e;
+        }
+
+        linear
+            .into_iter()
+            .map(|(s, e)| TimeRange { start: Clock::from_nanos(s), len: (e - s).min(NANOS_PER_DAY) })
+            .collect()
+    }
+
+    // None when the ranges neither overlap nor touch, since the result wouldn't be a single range
+    pub fn union(&self, other: &TimeRange) -> Option<TimeRange> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        let (first, second) = if self.offset(other.start) <= self.len {
+            (self, other)
+        } else if other.offset(self.start) <= other.len {
+            (other, self)
+        } else {
+            return None;
+        };
+        let len = first.len.max(first.offset(second.start) + second.len);
+        Some(TimeRange { start: first.start, len: len.min(NANOS_PER_DAY) })
+    }
+
+    // clocks from start (included) to end (excluded) every step
+    pub fn steps(&self, step: Duration) -> Steps {
+        assert!(!step.is_zero(), "step must be greater than zero");
+        Steps { range: *self, step: step.as_nanos().min(i64::MAX as u128) as i64, offset: 0 }
+    }
+}
+
+impl fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end())
+    }
+}
+
+pub struct Steps {
+    range: TimeRange,
+    step: i64,
+    offset: i64,
+}
+
+impl Iterator for Steps {
+    type Item = Clock;
+
+    fn next(&mut self) -> Option<Clock> {
+        if self.offset >= self.range.len {
+            return None;
+        }
+        let c = Clock::from_nanos(self.range.start.nanos + self.offset);
+        self.offset = self.offset.saturating_add(self.step);
+        Some(c)
+    }
+}
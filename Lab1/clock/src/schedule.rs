@@ -0,0 +1,307 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::{Clock, ClockParseError, DateTime, TimeRange, Weekday};
+
+// recurring schedule, written in a small english-like language:
+//   every weekday at 08:30 and 17:45
+//   every 20 minutes between 09:00 and 12:00
+//   every monday, friday at 9:00am
+//   every weekday at 9:00 am and 5:30 pm
+//   every weekend 2 hours between 22:00 and 06:00
+// the days part is optional (every day), "between" bounds are both included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    // indexed by Weekday as usize
+    days: [bool; 7],
+    // firing times in a day, sorted and without duplicates
+    times: Vec<Clock>,
+}
+
+// pos is the char offset of the offending word in the expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    UnexpectedWord { pos: usize, found: String, expected: &'static str },
+    UnexpectedEnd { expected: &'static str },
+    InvalidTime { pos: usize, error: ClockParseError },
+    ZeroInterval { pos: usize },
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScheduleError::UnexpectedWord { pos, found, expected } => {
+                write!(f, "unexpected '{}' at {}, expected {}", found, pos, expected)
+            }
+            ScheduleError::UnexpectedEnd { expected } => write!(f, "unexpected end of schedule, expected {}", expected),
+            ScheduleError::InvalidTime { pos, error } => write!(f, "invalid time at {}: {}", pos, error),
+            ScheduleError::ZeroInterval { pos } => write!(f, "interval at {} must be greater than zero", pos),
+        }
+    }
+}
+
+impl Error for ScheduleError {}
+
+// words with their char offset; commas are separators just like spaces
+fn tokenize(s: &str) -> Vec<(usize, String)> {
+    let mut tokens = vec![];
+    let mut current = String::new();
+    let mut start = 0;
+    for (i, c) in s.chars().enumerate() {
+        if c.is_whitespace() || c == ',' {
+            if !current.is_empty() {
+                push_token(&mut tokens, start, current.to_lowercase());
+                current.clear();
+            }
+        } else {
+            if current.is_empty() {
+                start = i;
+            }
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        push_token(&mut tokens, start, current.to_lowercase());
+    }
+    tokens
+}
+
+// "9:00 am" is a single time, so am/pm after a number goes with it
+fn push_token(tokens: &mut Vec<(usize, String)>, start: usize, token: String) {
+    if let Some((_, last)) = tokens.last_mut() {
+        if (token == "am" || token == "pm") && last.ends_with(|c: char| c.is_ascii_digit()) {
+            last.push_str(&token);
+            return;
+        }
+    }
+    tokens.push((start, token));
+}
+
+fn weekday_from_name(s: &str) -> Option<Weekday> {
+    let d = match s {
+        "monday" | "mon" => Weekday::Monday,
+        "tuesday" | "tue" => Weekday::Tuesday,
+        "wednesday" | "wed" => Weekday::Wednesday,
+        "thursday" | "thu" => Weekday::Thursday,
+        "friday" | "fri" => Weekday::Friday,
+        "saturday" | "sat" => Weekday::Saturday,
+        "sunday" | "sun" => Weekday::Sunday,
+        _ => return None,
+    };
+    Some(d)
+}
+
+struct Parser {
+    tokens: Vec<(usize, String)>,
+    next: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.next).map(|(_, t)| t.as_str())
+    }
+
+    fn bump(&mut self, expected: &'static str) -> Result<(usize, String), ScheduleError> {
+        match self.tokens.get(self.next) {
+            Some(t) => {
+                self.next += 1;
+                Ok(t.clone())
+            }
+            None => Err(ScheduleError::UnexpectedEnd { expected }),
+        }
+    }
+
+    fn keyword(&mut self, word: &'static str) -> Result<(), ScheduleError> {
+        let (pos, found) = self.bump(word)?;
+        if found != word {
+            return Err(ScheduleError::UnexpectedWord { pos, found, expected: word });
+        }
+        Ok(())
+    }
+
+    fn time(&mut self) -> Result<Clock, ScheduleError> {
+        let (pos, found) = self.bump("time")?;
+        found.parse().map_err(|error| ScheduleError::InvalidTime { pos, error })
+    }
+
+    fn days(&mut self) -> Result<[bool; 7], ScheduleError> {
+        let mut days = [false; 7];
+        let mut any = false;
+        loop {
+            match self.peek() {
+                Some("day") | Some("days") => days = [true; 7],
+                Some("weekday") | Some("weekdays") => days[..5].fill(true),
+                Some("weekend") | Some("weekends") => days[5..].fill(true),
+                Some("and") if any => {}
+                Some(t) => match weekday_from_name(t) {
+                    Some(d) => days[d as usize] = true,
+                    None => break,
+                },
+                None => break,
+            }
+            any = true;
+            self.next += 1;
+        }
+        // no days means every day
+        Ok(if days.contains(&true) { days } else { [true; 7] })
+    }
+
+    // "at" time ("and" time)*
+    fn at(&mut self) -> Result<Vec<Clock>, ScheduleError> {
+        let mut times = vec![self.time()?];
+        while self.peek().is_some() {
+            if self.peek() == Some("and") {
+                self.next += 1;
+            }
+            times.push(self.time()?);
+        }
+        Ok(times)
+    }
+
+    // number unit ["between" time "and" time]
+    fn interval(&mut self) -> Result<Vec<Clock>, ScheduleError> {
+        let (pos, found) = self.bump("interval")?;
+        let n: u64 = found
+            .parse()
+            .map_err(|_| ScheduleError::UnexpectedWord { pos, found: found.clone(), expected: "number" })?;
+        if n == 0 {
+            return Err(ScheduleError::ZeroInterval { pos });
+        }
+        let (upos, unit) = self.bump("minutes or hours")?;
+        let step = match unit.as_str() {
+            "minute" | "minutes" | "min" => Duration::from_secs(n * 60),
+            "hour" | "hours" => Duration::from_secs(n * 3600),
+            _ => return Err(ScheduleError::UnexpectedWord { pos: upos, found: unit, expected: "minutes or hours" }),
+        };
+
+        let (range, end) = if self.peek().is_some() {
+            self.keyword("between")?;
+            let start = self.time()?;
+            self.keyword("and")?;
+            let end = self.time()?;
+            if start == end {
+                (TimeRange::full_day(start), None)
+            } else {
+                (TimeRange::new(start, end), Some(end))
+            }
+        } else {
+            (TimeRange::full_day(Clock::new(0, 0)), None)
+        };
+
+        let mut times: Vec<Clock> = range.steps(step).collect();
+        // the end of "between" is included when it falls on a step
+        if let Some(end) = end {
+            if range.duration().as_nanos() % step.as_nanos() == 0 {
+                times.push(end);
+            }
+        }
+        Ok(times)
+    }
+
+    fn end(&self) -> Result<(), ScheduleError> {
+        match self.tokens.get(self.next) {
+            Some((pos, found)) => Err(ScheduleError::UnexpectedWord { pos: *pos, found: found.clone(), expected: "end of schedule" }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Schedule {
+    pub fn parse(s: &str) -> Result<Schedule, ScheduleError> {
+        let mut p = Parser { tokens: tokenize(s), next: 0 };
+        p.keyword("every")?;
+        let days = p.days()?;
+        let mut times = match p.peek() {
+            Some("at") => {
+                p.next += 1;
+                p.at()?
+            }
+            _ => p.interval()?,
+        };
+        p.end()?;
+
+        times.sort();
+        times.dedup();
+        Ok(Schedule { days, times })
+    }
+
+    pub fn times(&self) -> &[Clock] {
+        &self.times
+    }
+
+    pub fn runs_on(&self, day: Weekday) -> bool {
+        self.days[day as usize]
+    }
+
+    // first firing time strictly after c, possibly on the next day; days are not considered
+    pub fn next_after(&self, c: Clock) -> Option<Clock> {
+        self.times.iter().find(|&&t| t > c).or(self.times.first()).copied()
+    }
+
+    // all the firing times after c, forever
+    pub fn iter_from(&self, c: Clock) -> ScheduleIter<'_> {
+        ScheduleIter { schedule: self, current: c }
+    }
+
+    // first firing time strictly after dt on an allowed day
+    pub fn next_after_datetime(&self, dt: DateTime) -> Option<DateTime> {
+        // a week later we're back to the same day, so 8 days are always enough
+        for d in 0..=7 {
+            let date = dt.date().add_days(d);
+            if !self.runs_on(date.weekday()) {
+                continue;
+            }
+            let next = if d == 0 {
+                self.times.iter().find(|&&t| t > dt.time())
+            } else {
+                self.times.first()
+            };
+            if let Some(&t) = next {
+                return Some(DateTime::new(date, t));
+            }
+        }
+        None
+    }
+
+    pub fn iter_from_datetime(&self, dt: DateTime) -> DateTimeScheduleIter<'_> {
+        DateTimeScheduleIter { schedule: self, current: dt }
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Schedule::parse(s)
+    }
+}
+
+pub struct ScheduleIter<'a> {
+    schedule: &'a Schedule,
+    current: Clock,
+}
+
+impl Iterator for ScheduleIter<'_> {
+    type Item = Clock;
+
+    fn next(&mut self) -> Option<Clock> {
+        self.current = self.schedule.next_after(self.current)?;
+        Some(self.current)
+    }
+}
+
+pub struct DateTimeScheduleIter<'a> {
+    schedule: &'a Schedule,
+    current: DateTime,
+}
+
+impl Iterator for DateTimeScheduleIter<'_> {
+    type Item = DateTime;
+
+    fn next(&mut self) -> Option<DateTime> {
+        self.current = self.schedule.next_after_datetime(self.current)?;
+        Some(self.current)
+    }
+}
@@ -0,0 +1,130 @@
+use clock::{AlarmScheduler, Clock, FakeTimeSource, SystemTimeSource, TimeSource};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+fn minutes(m: u64) -> Duration {
+    Duration::from_secs(m * 60)
+}
+
+// scheduler plus a log of the fired alarm times
+fn setup(start: Clock) -> (FakeTimeSource, AlarmScheduler<FakeTimeSource>, Rc<RefCell<Vec<String>>>) {
+    let time = FakeTimeSource::new(start);
+    let scheduler = AlarmScheduler::new(time.clone());
+    (time, scheduler, Rc::new(RefCell::new(vec![])))
+}
+
+fn recorder(log: &Rc<RefCell<Vec<String>>>) -> impl FnMut(Clock) + 'static {
+    let log = Rc::clone(log);
+    move |c| log.borrow_mut().push(c.to_string())
+}
+
+#[test]
+fn test_fake_time_source() {
+    let time = FakeTimeSource::new(Clock::new(10, 0));
+    let handle = time.clone();
+    handle.advance(minutes(90));
+    assert_eq!(time.now(), Clock::new(11, 30));
+    handle.set(Clock::new(1, 0));
+    assert_eq!(time.now(), Clock::new(1, 0));
+}
+
+#[test]
+fn test_system_time_source_is_a_valid_clock() {
+    let now = SystemTimeSource.now();
+    assert!(now.hours() < 24);
+}
+
+#[test]
+fn test_alarm_does_not_fire_before_its_time() {
+    let (time, mut s, log) = setup(Clock::new(8, 0));
+    s.add(Clock::new(8, 30), recorder(&log));
+    time.advance(minutes(29));
+    assert!(s.tick().is_empty());
+    assert!(log.borrow().is_empty());
+}
+
+#[test]
+fn test_alarm_fires_at_its_time() {
+    let (time, mut s, log) = setup(Clock::new(8, 0));
+    let id = s.add(Clock::new(8, 30), recorder(&log));
+    time.advance(minutes(30));
+    assert_eq!(s.tick(), vec![id]);
+    assert_eq!(*log.borrow(), vec!["08:30"]);
+}
+
+#[test]
+fn test_alarm_fires_once_per_pass() {
+    let (time, mut s, log) = setup(Clock::new(8, 0));
+    s.add(Clock::new(8, 30), recorder(&log));
+    time.advance(minutes(45));
+    s.tick();
+    time.advance(minutes(1));
+    assert!(s.tick().is_empty());
+    assert_eq!(log.borrow().len(), 1);
+}
+
+#[test]
+fn test_tick_without_time_passing() {
+    let (_time, mut s, log) = setup(Clock::new(8, 0));
+    s.add(Clock::new(8, 0), recorder(&log));
+    assert!(s.tick().is_empty());
+}
+
+#[test]
+fn test_alarms_fire_in_chronological_order_across_midnight() {
+    let (time, mut s, log) = setup(Clock::new(23, 0));
+    s.add(Clock::new(0, 30), recorder(&log));
+    s.add(Clock::new(23, 30), recorder(&log));
+    s.add(Clock::new(2, 0), recorder(&log));
+    time.advance(minutes(120));
+    assert_eq!(s.tick().len(), 2);
+    assert_eq!(*log.borrow(), vec!["23:30", "00:30"]);
+}
+
+#[test]
+fn test_repeating_alarm_fires_every_day() {
+    let (time, mut s, log) = setup(Clock::new(6, 0));
+    s.add(Clock::new(7, 0), recorder(&log));
+    for _ in 0..3 {
+        time.advance(minutes(12 * 60));
+        s.tick();
+        time.advance(minutes(12 * 60));
+        s.tick();
+    }
+    assert_eq!(log.borrow().len(), 3);
+    assert_eq!(s.pending(), 1);
+}
+
+#[test]
+fn test_once_alarm_is_removed() {
+    let (time, mut s, log) = setup(Clock::new(6, 0));
+    s.add_once(Clock::new(7, 0), recorder(&log));
+    time.advance(minutes(12 * 60));
+    s.tick();
+    assert_eq!(s.pending(), 0);
+    time.advance(minutes(12 * 60));
+    s.tick();
+    assert_eq!(log.borrow().len(), 1);
+}
+
+#[test]
+fn test_cancel() {
+    let (time, mut s, log) = setup(Clock::new(6, 0));
+    let id = s.add(Clock::new(7, 0), recorder(&log));
+    assert!(s.cancel(id));
+    assert!(!s.cancel(id));
+    time.advance(minutes(60));
+    assert!(s.tick().is_empty());
+}
+
+#[test]
+fn test_set_time_backwards_wraps_to_next_day() {
+    // going from 10:00 to 09:00 means almost a whole day passed
+    let (time, mut s, log) = setup(Clock::new(10, 0));
+    s.add(Clock::new(20, 0), recorder(&log));
+    s.add(Clock::new(9, 30), recorder(&log));
+    time.set(Clock::new(9, 0));
+    s.tick();
+    assert_eq!(*log.borrow(), vec!["20:00"]);
+}
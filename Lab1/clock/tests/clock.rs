@@ -1,4 +1,5 @@
-use clock::Clock;
+use clock::{Clock, TimeDelta};
+use std::time::Duration;
 
 //
 // Clock Creation
@@ -350,3 +351,125 @@ fn test_compare_clocks_with_negative_hours_and_minutes_that_wrap() {
 fn test_compare_full_clock_and_zeroed_clock() {
     assert_eq!(Clock::new(24, 0), Clock::new(0, 0));
 }
+
+//
+// Seconds and nanoseconds
+//
+
+#[test]
+fn test_clock_with_seconds() {
+    let clock = Clock::from_hms(9, 15, 42);
+    assert_eq!((clock.hours(), clock.minutes(), clock.seconds()), (9, 15, 42));
+}
+
+#[test]
+fn test_display_ignores_seconds() {
+    assert_eq!(Clock::from_hms(9, 15, 42).to_string(), "09:15");
+}
+
+#[test]
+fn test_seconds_roll_over() {
+    assert_eq!(Clock::from_hms(23, 59, 61), Clock::from_hms(0, 0, 1));
+}
+
+#[test]
+fn test_negative_seconds() {
+    assert_eq!(Clock::from_hms(0, 0, -1), Clock::from_hms(23, 59, 59));
+}
+
+#[test]
+fn test_nanoseconds_are_kept() {
+    let clock = Clock::from_hms_nano(12, 0, 0, 1_500_000_000);
+    assert_eq!(clock.seconds(), 1);
+    assert_eq!(clock.nanoseconds(), 500_000_000);
+}
+
+#[test]
+fn test_clock_with_seconds_differs_from_whole_minute() {
+    assert_ne!(Clock::from_hms(10, 0, 1), Clock::new(10, 0));
+}
+
+#[test]
+fn test_add_seconds_across_midnight() {
+    assert_eq!(Clock::from_hms(23, 59, 30).add_seconds(45), Clock::from_hms(0, 0, 15));
+}
+
+#[test]
+fn test_add_minutes_keeps_seconds() {
+    assert_eq!(Clock::from_hms(10, 0, 30).add_minutes(-1), Clock::from_hms(9, 59, 30));
+}
+
+#[test]
+fn test_since_midnight() {
+    assert_eq!(Clock::from_hms(1, 0, 1).since_midnight(), Duration::from_secs(3601));
+}
+
+//
+// Duration arithmetic
+//
+
+#[test]
+fn test_add_duration() {
+    assert_eq!(Clock::new(10, 0) + Duration::from_secs(90), Clock::from_hms(10, 1, 30));
+}
+
+#[test]
+fn test_add_duration_longer_than_a_day() {
+    let clock = Clock::new(5, 32) + Duration::from_secs(1500 * 60);
+    assert_eq!(clock, Clock::new(6, 32));
+}
+
+#[test]
+fn test_sub_duration_across_midnight() {
+    let clock = Clock::new(0, 3) - Duration::from_secs(4 * 60);
+    assert_eq!(clock, Clock::new(23, 59));
+}
+
+#[test]
+fn test_add_assign_and_sub_assign_duration() {
+    let mut clock = Clock::new(12, 0);
+    clock += Duration::from_millis(1500);
+    assert_eq!(clock, Clock::from_hms_nano(12, 0, 1, 500_000_000));
+    clock -= Duration::from_millis(1500);
+    assert_eq!(clock, Clock::new(12, 0));
+}
+
+//
+// Clock difference
+//
+
+fn minutes(m: u64) -> Duration {
+    Duration::from_secs(m * 60)
+}
+
+#[test]
+fn test_difference_same_day() {
+    assert_eq!(Clock::new(10, 30) - Clock::new(10, 0), TimeDelta::forward(minutes(30)));
+    assert_eq!(Clock::new(10, 0) - Clock::new(10, 30), TimeDelta::backward(minutes(30)));
+}
+
+#[test]
+fn test_difference_is_shortest_across_midnight() {
+    assert_eq!(Clock::new(0, 10) - Clock::new(23, 50), TimeDelta::forward(minutes(20)));
+    assert_eq!(Clock::new(23, 50) - Clock::new(0, 10), TimeDelta::backward(minutes(20)));
+}
+
+#[test]
+fn test_difference_of_half_a_day_is_positive() {
+    assert_eq!(Clock::new(12, 0) - Clock::new(0, 0), TimeDelta::forward(minutes(12 * 60)));
+    assert_eq!(Clock::new(0, 0) - Clock::new(12, 0), TimeDelta::forward(minutes(12 * 60)));
+}
+
+#[test]
+fn test_difference_of_equal_clocks_is_zero() {
+    let zero = Clock::from_hms(8, 8, 8) - Clock::from_hms(8, 8, 8);
+    assert_eq!(zero, TimeDelta::backward(Duration::ZERO));
+    assert!(!zero.is_negative());
+}
+
+#[test]
+fn test_difference_keeps_the_nanoseconds() {
+    let delta = Clock::from_hms(8, 0, 0) - Clock::from_hms_nano(8, 0, 1, 500);
+    assert!(delta.is_negative());
+    assert_eq!(delta.duration(), Duration::new(1, 500));
+}
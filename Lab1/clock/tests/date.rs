@@ -0,0 +1,163 @@
+use clock::{days_in_month, is_leap_year, Clock, Date, DateTime, TimeDelta, Weekday};
+use std::time::Duration;
+
+fn date(y: i32, m: u32, d: u32) -> Date {
+    Date::new(y, m, d).unwrap()
+}
+
+//
+// Day carry
+//
+
+#[test]
+fn test_carry_without_crossing_midnight() {
+    assert_eq!(Clock::new(10, 0).add_minutes_with_carry(30), (Clock::new(10, 30), 0));
+}
+
+#[test]
+fn test_carry_two_days() {
+    assert_eq!(Clock::new(0, 0).add_minutes_with_carry(3000), (Clock::new(2, 0), 2));
+}
+
+#[test]
+fn test_carry_exactly_midnight() {
+    assert_eq!(Clock::new(23, 0).add_minutes_with_carry(60), (Clock::new(0, 0), 1));
+}
+
+#[test]
+fn test_carry_backwards() {
+    assert_eq!(Clock::new(2, 20).add_minutes_with_carry(-3000), (Clock::new(0, 20), -2));
+    assert_eq!(Clock::new(0, 0).add_minutes_with_carry(-1), (Clock::new(23, 59), -1));
+}
+
+#[test]
+fn test_carry_huge_value() {
+    let (_, days) = Clock::new(0, 0).add_minutes_with_carry(i32::MAX);
+    assert_eq!(days, i32::MAX / 1440);
+}
+
+//
+// Calendar
+//
+
+#[test]
+fn test_leap_years() {
+    assert!(is_leap_year(2024));
+    assert!(is_leap_year(2000));
+    assert!(!is_leap_year(1900));
+    assert!(!is_leap_year(2023));
+}
+
+#[test]
+fn test_days_in_february() {
+    assert_eq!(days_in_month(2024, 2), 29);
+    assert_eq!(days_in_month(2100, 2), 28);
+}
+
+#[test]
+fn test_invalid_dates() {
+    assert_eq!(Date::new(2023, 2, 29), None);
+    assert_eq!(Date::new(2023, 13, 1), None);
+    assert_eq!(Date::new(2023, 4, 0), None);
+    assert_eq!(Date::new(2023, 4, 31), None);
+}
+
+#[test]
+fn test_add_days_across_leap_day() {
+    assert_eq!(date(2024, 2, 28).add_days(1), date(2024, 2, 29));
+    assert_eq!(date(2024, 2, 28).add_days(2), date(2024, 3, 1));
+    assert_eq!(date(2023, 2, 28).add_days(1), date(2023, 3, 1));
+}
+
+#[test]
+fn test_add_days_across_years() {
+    assert_eq!(date(2023, 12, 31).add_days(1), date(2024, 1, 1));
+    assert_eq!(date(2024, 1, 1).add_days(-1), date(2023, 12, 31));
+    assert_eq!(date(2024, 1, 1).add_days(366), date(2025, 1, 1));
+}
+
+#[test]
+fn test_days_since() {
+    assert_eq!(date(2024, 3, 1).days_since(date(2024, 2, 1)), 29);
+    assert_eq!(date(2000, 1, 1).days_since(date(1970, 1, 1)), 10957);
+}
+
+#[test]
+fn test_weekday() {
+    assert_eq!(date(1970, 1, 1).weekday(), Weekday::Thursday);
+    assert_eq!(date(2024, 2, 29).weekday(), Weekday::Thursday);
+    assert_eq!(date(2026, 10, 18).weekday(), Weekday::Sunday);
+    assert_eq!(date(1900, 1, 1).weekday(), Weekday::Monday);
+}
+
+#[test]
+fn test_ordinal() {
+    assert_eq!(date(2024, 12, 31).ordinal(), 366);
+    assert_eq!(date(2023, 3, 1).ordinal(), 60);
+}
+
+#[test]
+fn test_date_ordering() {
+    assert!(date(2023, 12, 31) < date(2024, 1, 1));
+    assert!(date(2024, 2, 1) > date(2024, 1, 31));
+}
+
+#[test]
+fn test_date_display() {
+    assert_eq!(date(2024, 2, 9).to_string(), "2024-02-09");
+}
+
+//
+// DateTime
+//
+
+#[test]
+fn test_datetime_add_minutes_moves_the_date() {
+    let dt = DateTime::new(date(2024, 2, 28), Clock::new(22, 0)).add_minutes(3000);
+    assert_eq!(dt, DateTime::new(date(2024, 3, 2), Clock::new(0, 0)));
+    assert_eq!(dt.to_string(), "2024-03-02T00:00:00");
+}
+
+#[test]
+fn test_datetime_add_minutes_backwards() {
+    let dt = DateTime::new(date(2024, 1, 1), Clock::new(0, 30)).add_minutes(-60);
+    assert_eq!(dt, DateTime::new(date(2023, 12, 31), Clock::new(23, 30)));
+}
+
+#[test]
+fn test_datetime_add_duration() {
+    let dt = DateTime::new(date(2024, 2, 29), Clock::new(23, 59)) + Duration::from_secs(120);
+    assert_eq!(dt, DateTime::new(date(2024, 3, 1), Clock::new(0, 1)));
+    assert_eq!(dt - Duration::from_secs(120), DateTime::new(date(2024, 2, 29), Clock::new(23, 59)));
+}
+
+#[test]
+fn test_datetime_difference_counts_days() {
+    let start = DateTime::new(date(2024, 3, 1), Clock::new(22, 0));
+    let end = DateTime::new(date(2024, 3, 3), Clock::new(6, 0));
+    assert_eq!(end - start, TimeDelta::forward(Duration::from_secs(32 * 3600)));
+    assert_eq!(start - end, TimeDelta::backward(Duration::from_secs(32 * 3600)));
+}
+
+#[test]
+fn test_datetime_difference_over_centuries() {
+    let start = DateTime::new(date(1600, 1, 1), Clock::from_hms_nano(0, 0, 0, 1));
+    let end = DateTime::new(date(2400, 1, 1), Clock::new(0, 0));
+    let days = date(2400, 1, 1).days_since(date(1600, 1, 1)) as u64;
+    assert_eq!(end - start, TimeDelta::forward(Duration::from_secs(days * 24 * 3600) - Duration::from_nanos(1)));
+}
+
+#[test]
+fn test_datetime_display_keeps_seconds() {
+    assert_eq!(DateTime::new(date(2024, 2, 29), Clock::from_hms(9, 15, 42)).to_string(), "2024-02-29T09:15:42");
+    let dt = DateTime::new(date(2024, 2, 29), Clock::from_hms_nano(9, 15, 42, 5_000));
+    assert_eq!(dt.to_string(), "2024-02-29T09:15:42.000005000");
+}
+
+#[test]
+fn test_datetime_ordering() {
+    let a = DateTime::new(date(2024, 3, 1), Clock::new(23, 0));
+    let b = DateTime::new(date(2024, 3, 2), Clock::new(1, 0));
+    assert!(a < b);
+    assert_eq!(b.weekday(), Weekday::Saturday);
+}
@@ -0,0 +1,112 @@
+use clock::{Clock, ClockParseError};
+
+#[test]
+fn test_format_24_hours() {
+    assert_eq!(Clock::new(21, 5).format("%H:%M"), "21:05");
+    assert_eq!(Clock::new(21, 5).format("%Hh%M"), "21h05");
+}
+
+#[test]
+fn test_format_12_hours() {
+    assert_eq!(Clock::new(21, 5).format("%-I:%M %p"), "9:05 PM");
+    assert_eq!(Clock::new(9, 5).format("%I:%M%P"), "09:05am");
+}
+
+#[test]
+fn test_format_12_hours_midnight_and_noon() {
+    assert_eq!(Clock::new(0, 0).format("%I %p"), "12 AM");
+    assert_eq!(Clock::new(12, 0).format("%I %p"), "12 PM");
+}
+
+#[test]
+fn test_format_seconds_and_nanoseconds() {
+    let clock = Clock::from_hms_nano(7, 8, 9, 1_000);
+    assert_eq!(clock.format("%H:%M:%S.%f"), "07:08:09.000001000");
+    assert_eq!(clock.format("%-H:%-M:%-S"), "7:8:9");
+}
+
+#[test]
+fn test_format_literal_percent_and_unknown_specifier() {
+    assert_eq!(Clock::new(10, 0).format("%H%% %q %-q %"), "10% %q %-q %");
+}
+
+#[test]
+fn test_display_is_hours_and_minutes() {
+    assert_eq!(format!("{}", Clock::from_hms(9, 15, 42)), "09:15");
+}
+
+#[test]
+fn test_parse_with_12_hours() {
+    assert_eq!(Clock::parse_with("%-I:%M %p", "9:05 PM"), Ok(Clock::new(21, 5)));
+    assert_eq!(Clock::parse_with("%-I:%M %p", "12:30 am"), Ok(Clock::new(0, 30)));
+}
+
+#[test]
+fn test_parse_with_custom_separator() {
+    assert_eq!(Clock::parse_with("%Hh%M", "21h05"), Ok(Clock::new(21, 5)));
+}
+
+#[test]
+fn test_parse_with_seconds_and_fraction() {
+    assert_eq!(
+        Clock::parse_with("%H:%M:%S.%f", "07:08:09.5"),
+        Ok(Clock::from_hms_nano(7, 8, 9, 500_000_000))
+    );
+}
+
+#[test]
+fn test_parse_with_literal_percent() {
+    assert_eq!(Clock::parse_with("%H%%%M", "10%30"), Ok(Clock::new(10, 30)));
+}
+
+#[test]
+fn test_parse_with_round_trip() {
+    let clock = Clock::from_hms(23, 4, 5);
+    for pattern in ["%H:%M:%S", "%-I:%M:%S %p", "%I.%M.%S%P", "%Hh%Mm%Ss"] {
+        assert_eq!(Clock::parse_with(pattern, &clock.format(pattern)), Ok(clock));
+    }
+}
+
+#[test]
+fn test_parse_with_mismatched_literal() {
+    assert_eq!(
+        Clock::parse_with("%H:%M", "21h05"),
+        Err(ClockParseError::UnexpectedChar { pos: 2, found: 'h', expected: "pattern literal" })
+    );
+}
+
+#[test]
+fn test_parse_with_literals_are_case_sensitive() {
+    assert_eq!(
+        Clock::parse_with("%Hh%M", "21H05"),
+        Err(ClockParseError::UnexpectedChar { pos: 2, found: 'H', expected: "pattern literal" })
+    );
+    // am/pm is not a literal
+    assert_eq!(Clock::parse_with("%Ih%M%p", "09h05Pm"), Ok(Clock::new(21, 5)));
+}
+
+#[test]
+fn test_parse_with_missing_meridiem() {
+    assert_eq!(
+        Clock::parse_with("%I:%M %p", "09:05 "),
+        Err(ClockParseError::UnexpectedEnd { pos: 6, expected: "am/pm" })
+    );
+}
+
+#[test]
+fn test_parse_with_out_of_range() {
+    assert_eq!(
+        Clock::parse_with("%I:%M", "13:00"),
+        Err(ClockParseError::OutOfRange { pos: 0, field: "hour", value: 13 })
+    );
+}
+
+#[test]
+fn test_parse_with_invalid_pattern() {
+    assert_eq!(Clock::parse_with("%H:%q", "10:00"), Err(ClockParseError::InvalidPattern { pos: 3 }));
+}
+
+#[test]
+fn test_parse_with_trailing_input() {
+    assert_eq!(Clock::parse_with("%H:%M", "10:00:00").unwrap_err().position(), 5);
+}
@@ -0,0 +1,120 @@
+use clock::{Clock, ClockParseError};
+
+#[test]
+fn test_parse_24_hours() {
+    assert_eq!("14:30".parse::<Clock>(), Ok(Clock::new(14, 30)));
+}
+
+#[test]
+fn test_parse_single_digit_hour() {
+    assert_eq!("9:05".parse::<Clock>(), Ok(Clock::new(9, 5)));
+}
+
+#[test]
+fn test_parse_with_seconds() {
+    assert_eq!("09:15:42".parse::<Clock>(), Ok(Clock::from_hms(9, 15, 42)));
+}
+
+#[test]
+fn test_parse_with_fraction_of_second() {
+    assert_eq!(
+        "09:15:42.25".parse::<Clock>(),
+        Ok(Clock::from_hms_nano(9, 15, 42, 250_000_000))
+    );
+}
+
+#[test]
+fn test_parse_12_hours() {
+    assert_eq!("9:05 pm".parse::<Clock>(), Ok(Clock::new(21, 5)));
+    assert_eq!("9:05 AM".parse::<Clock>(), Ok(Clock::new(9, 5)));
+    assert_eq!("11:59pm".parse::<Clock>(), Ok(Clock::new(23, 59)));
+}
+
+#[test]
+fn test_parse_12_hours_midnight_and_noon() {
+    assert_eq!("12:00 am".parse::<Clock>(), Ok(Clock::new(0, 0)));
+    assert_eq!("12:00 pm".parse::<Clock>(), Ok(Clock::new(12, 0)));
+}
+
+#[test]
+fn test_parse_iso() {
+    assert_eq!("T14:30Z".parse::<Clock>(), Ok(Clock::new(14, 30)));
+    assert_eq!("T14:30:15Z".parse::<Clock>(), Ok(Clock::from_hms(14, 30, 15)));
+    assert_eq!("T14:30".parse::<Clock>(), Ok(Clock::new(14, 30)));
+}
+
+#[test]
+fn test_parse_iso_offset_is_converted_to_utc() {
+    assert_eq!("T01:30+02:00".parse::<Clock>(), Ok(Clock::new(23, 30)));
+    assert_eq!("T22:00-0330".parse::<Clock>(), Ok(Clock::new(1, 30)));
+}
+
+#[test]
+fn test_try_from() {
+    assert_eq!(Clock::try_from("00:00"), Ok(Clock::new(0, 0)));
+}
+
+#[test]
+fn test_parse_empty() {
+    assert_eq!("".parse::<Clock>(), Err(ClockParseError::Empty));
+}
+
+#[test]
+fn test_parse_hour_out_of_range() {
+    assert_eq!(
+        "24:00".parse::<Clock>(),
+        Err(ClockParseError::OutOfRange { pos: 0, field: "hour", value: 24 })
+    );
+}
+
+#[test]
+fn test_parse_minute_out_of_range() {
+    assert_eq!(
+        "10:60".parse::<Clock>(),
+        Err(ClockParseError::OutOfRange { pos: 3, field: "minute", value: 60 })
+    );
+}
+
+#[test]
+fn test_parse_12_hours_hour_out_of_range() {
+    assert_eq!(
+        "13:00 pm".parse::<Clock>(),
+        Err(ClockParseError::OutOfRange { pos: 0, field: "hour", value: 13 })
+    );
+    assert_eq!("0:30 am".parse::<Clock>().unwrap_err().position(), 0);
+}
+
+#[test]
+fn test_parse_missing_colon() {
+    assert_eq!(
+        "1430".parse::<Clock>(),
+        Err(ClockParseError::UnexpectedChar { pos: 2, found: '3', expected: "':'" })
+    );
+}
+
+#[test]
+fn test_parse_single_digit_minute() {
+    assert_eq!(
+        "10:5".parse::<Clock>(),
+        Err(ClockParseError::UnexpectedEnd { pos: 4, expected: "minute" })
+    );
+}
+
+#[test]
+fn test_parse_trailing_garbage() {
+    assert_eq!(
+        "10:30x".parse::<Clock>(),
+        Err(ClockParseError::UnexpectedChar { pos: 5, found: 'x', expected: "end of input" })
+    );
+}
+
+#[test]
+fn test_parse_iso_requires_two_digit_hour() {
+    assert_eq!("T9:30Z".parse::<Clock>().unwrap_err().position(), 2);
+}
+
+#[test]
+fn test_parse_error_message() {
+    let err = "10:60".parse::<Clock>().unwrap_err();
+    assert_eq!(err.to_string(), "minute 60 out of range at 3");
+}
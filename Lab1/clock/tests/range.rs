@@ -0,0 +1,126 @@
+use clock::{Clock, TimeRange};
+use std::time::Duration;
+
+fn range(h1: i32, m1: i32, h2: i32, m2: i32) -> TimeRange {
+    TimeRange::new(Clock::new(h1, m1), Clock::new(h2, m2))
+}
+
+#[test]
+fn test_duration() {
+    assert_eq!(range(9, 0, 17, 30).duration(), Duration::from_secs(510 * 60));
+}
+
+#[test]
+fn test_duration_across_midnight() {
+    assert_eq!(range(22, 0, 6, 0).duration(), Duration::from_secs(8 * 3600));
+    assert!(range(22, 0, 6, 0).wraps_midnight());
+}
+
+#[test]
+fn test_empty_and_full_day() {
+    assert!(range(10, 0, 10, 0).is_empty());
+    let day = TimeRange::full_day(Clock::new(10, 0));
+    assert_eq!(day.duration(), Duration::from_secs(24 * 3600));
+    assert!(day.contains(Clock::new(9, 59)));
+}
+
+#[test]
+fn test_from_duration_is_capped_to_a_day() {
+    let r = TimeRange::from_duration(Clock::new(6, 0), Duration::from_secs(30 * 3600));
+    assert_eq!(r, TimeRange::full_day(Clock::new(6, 0)));
+}
+
+#[test]
+fn test_contains_is_half_open() {
+    let r = range(9, 0, 17, 0);
+    assert!(r.contains(Clock::new(9, 0)));
+    assert!(r.contains(Clock::new(16, 59)));
+    assert!(!r.contains(Clock::new(17, 0)));
+    assert!(!r.contains(Clock::new(8, 59)));
+}
+
+#[test]
+fn test_contains_across_midnight() {
+    let r = range(22, 0, 6, 0);
+    assert!(r.contains(Clock::new(23, 30)));
+    assert!(r.contains(Clock::new(0, 0)));
+    assert!(r.contains(Clock::new(5, 59)));
+    assert!(!r.contains(Clock::new(6, 0)));
+    assert!(!r.contains(Clock::new(12, 0)));
+}
+
+#[test]
+fn test_overlaps() {
+    assert!(range(22, 0, 6, 0).overlaps(&range(5, 0, 7, 0)));
+    assert!(range(22, 0, 6, 0).overlaps(&range(23, 0, 1, 0)));
+    assert!(!range(22, 0, 6, 0).overlaps(&range(6, 0, 22, 0)));
+    assert!(!range(9, 0, 10, 0).overlaps(&range(10, 0, 11, 0)));
+}
+
+#[test]
+fn test_intersection() {
+    assert_eq!(range(9, 0, 12, 0).intersection(&range(11, 0, 14, 0)), vec![range(11, 0, 12, 0)]);
+    assert_eq!(range(9, 0, 10, 0).intersection(&range(11, 0, 14, 0)), vec![]);
+}
+
+#[test]
+fn test_intersection_across_midnight() {
+    assert_eq!(range(22, 0, 6, 0).intersection(&range(23, 0, 2, 0)), vec![range(23, 0, 2, 0)]);
+    assert_eq!(range(22, 0, 6, 0).intersection(&range(20, 0, 4, 0)), vec![range(22, 0, 4, 0)]);
+}
+
+#[test]
+fn test_intersection_in_two_pieces() {
+    assert_eq!(
+        range(22, 0, 6, 0).intersection(&range(5, 0, 23, 0)),
+        vec![range(5, 0, 6, 0), range(22, 0, 23, 0)]
+    );
+}
+
+#[test]
+fn test_union() {
+    assert_eq!(range(9, 0, 12, 0).union(&range(11, 0, 14, 0)), Some(range(9, 0, 14, 0)));
+    assert_eq!(range(11, 0, 14, 0).union(&range(9, 0, 12, 0)), Some(range(9, 0, 14, 0)));
+    assert_eq!(range(9, 0, 10, 0).union(&range(10, 0, 11, 0)), Some(range(9, 0, 11, 0)));
+    assert_eq!(range(9, 0, 10, 0).union(&range(11, 0, 12, 0)), None);
+}
+
+#[test]
+fn test_union_across_midnight() {
+    assert_eq!(range(22, 0, 2, 0).union(&range(1, 0, 6, 0)), Some(range(22, 0, 6, 0)));
+    assert_eq!(range(18, 0, 22, 0).union(&range(22, 0, 6, 0)), Some(range(18, 0, 6, 0)));
+}
+
+#[test]
+fn test_union_covering_the_whole_day() {
+    let u = range(22, 0, 6, 0).union(&range(5, 0, 23, 0)).unwrap();
+    assert_eq!(u.duration(), Duration::from_secs(24 * 3600));
+}
+
+#[test]
+fn test_steps() {
+    let steps: Vec<String> = range(9, 0, 10, 0).steps(Duration::from_secs(15 * 60)).map(|c| c.to_string()).collect();
+    assert_eq!(steps, vec!["09:00", "09:15", "09:30", "09:45"]);
+}
+
+#[test]
+fn test_steps_across_midnight() {
+    let steps: Vec<String> = range(23, 0, 1, 0).steps(Duration::from_secs(30 * 60)).map(|c| c.to_string()).collect();
+    assert_eq!(steps, vec!["23:00", "23:30", "00:00", "00:30"]);
+}
+
+#[test]
+fn test_steps_not_multiple_of_range() {
+    assert_eq!(range(9, 0, 10, 0).steps(Duration::from_secs(25 * 60)).count(), 3);
+}
+
+#[test]
+#[should_panic]
+fn test_steps_of_zero() {
+    let _ = range(9, 0, 10, 0).steps(Duration::ZERO);
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(range(22, 0, 6, 0).to_string(), "22:00-06:00");
+}
@@ -0,0 +1,199 @@
+use clock::{Clock, Date, DateTime, Schedule, ScheduleError, Weekday};
+
+fn schedule(s: &str) -> Schedule {
+    s.parse().unwrap()
+}
+
+fn at(d: u32, h: i32, m: i32) -> DateTime {
+    // 2024-03-04 is a monday
+    DateTime::new(Date::new(2024, 3, d).unwrap(), Clock::new(h, m))
+}
+
+//
+// Parsing
+//
+
+#[test]
+fn test_parse_at_times() {
+    let s = schedule("every weekday at 17:45 and 08:30");
+    assert_eq!(s.times(), &[Clock::new(8, 30), Clock::new(17, 45)]);
+    assert!(s.runs_on(Weekday::Friday));
+    assert!(!s.runs_on(Weekday::Saturday));
+}
+
+#[test]
+fn test_parse_without_days_is_every_day() {
+    let s = schedule("every 20 minutes between 09:00 and 12:00");
+    assert!(s.runs_on(Weekday::Sunday));
+    assert_eq!(s.times().len(), 10);
+    assert_eq!(s.times()[0], Clock::new(9, 0));
+    assert_eq!(s.times()[9], Clock::new(12, 0));
+}
+
+#[test]
+fn test_parse_day_names_and_commas() {
+    let s = schedule("Every Mon, friday and sun at 9:00am, 9:00pm");
+    assert_eq!(s.times(), &[Clock::new(9, 0), Clock::new(21, 0)]);
+    let days: Vec<bool> = (0..7).map(|d| s.runs_on(Weekday::from(d))).collect();
+    assert_eq!(days, vec![true, false, false, false, true, false, true]);
+}
+
+#[test]
+fn test_parse_meridiem_after_a_space() {
+    let s = schedule("every weekday at 9:00 am and 5:30 PM");
+    assert_eq!(s.times(), &[Clock::new(9, 0), Clock::new(17, 30)]);
+    assert_eq!(schedule("every 3 hours between 9:00 am and 3:00 pm").times().len(), 3);
+    assert!(matches!(
+        "every day at 13:00 pm".parse::<Schedule>(),
+        Err(ScheduleError::InvalidTime { pos: 13, .. })
+    ));
+}
+
+#[test]
+fn test_parse_whole_day_interval() {
+    assert_eq!(schedule("every 2 hours").times().len(), 12);
+    assert_eq!(schedule("every day 1 minute").times().len(), 1440);
+}
+
+#[test]
+fn test_parse_duplicate_times() {
+    assert_eq!(schedule("every day at 10:00 10:00").times(), &[Clock::new(10, 0)]);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        "each day at 10:00".parse::<Schedule>(),
+        Err(ScheduleError::UnexpectedWord { pos: 0, found: "each".to_string(), expected: "every" })
+    );
+    assert_eq!("every day at".parse::<Schedule>(), Err(ScheduleError::UnexpectedEnd { expected: "time" }));
+    assert_eq!("every 0 minutes".parse::<Schedule>(), Err(ScheduleError::ZeroInterval { pos: 6 }));
+    assert_eq!(
+        "every 5 seconds".parse::<Schedule>(),
+        Err(ScheduleError::UnexpectedWord { pos: 8, found: "seconds".to_string(), expected: "minutes or hours" })
+    );
+    assert!(matches!(
+        "every day at 25:00".parse::<Schedule>(),
+        Err(ScheduleError::InvalidTime { pos: 13, .. })
+    ));
+    assert!(matches!(
+        "every 5 minutes from 10:00".parse::<Schedule>(),
+        Err(ScheduleError::UnexpectedWord { pos: 16, .. })
+    ));
+}
+
+//
+// Next firing time
+//
+
+#[test]
+fn test_next_after_same_day() {
+    let s = schedule("every day at 08:30 and 17:45");
+    assert_eq!(s.next_after(Clock::new(7, 0)), Some(Clock::new(8, 30)));
+    assert_eq!(s.next_after(Clock::new(9, 0)), Some(Clock::new(17, 45)));
+}
+
+#[test]
+fn test_next_after_is_strict() {
+    let s = schedule("every day at 08:30 and 17:45");
+    assert_eq!(s.next_after(Clock::new(8, 30)), Some(Clock::new(17, 45)));
+}
+
+#[test]
+fn test_next_after_wraps_past_midnight() {
+    let s = schedule("every day at 08:30 and 17:45");
+    assert_eq!(s.next_after(Clock::new(17, 45)), Some(Clock::new(8, 30)));
+    assert_eq!(s.next_after(Clock::new(23, 59)), Some(Clock::new(8, 30)));
+}
+
+#[test]
+fn test_next_after_single_time_is_a_day_later() {
+    let s = schedule("every day at 00:00");
+    assert_eq!(s.next_after(Clock::new(0, 0)), Some(Clock::new(0, 0)));
+}
+
+#[test]
+fn test_interval_across_midnight() {
+    let s = schedule("every 30 minutes between 23:00 and 01:00");
+    assert_eq!(
+        s.iter_from(Clock::new(22, 0)).take(6).map(|c| c.to_string()).collect::<Vec<_>>(),
+        vec!["23:00", "23:30", "00:00", "00:30", "01:00", "23:00"]
+    );
+}
+
+#[test]
+fn test_interval_end_not_on_a_step() {
+    let s = schedule("every 25 minutes between 23:30 and 00:30");
+    assert_eq!(s.times(), &[Clock::new(0, 20), Clock::new(23, 30), Clock::new(23, 55)]);
+    assert_eq!(s.next_after(Clock::new(23, 56)), Some(Clock::new(0, 20)));
+    assert_eq!(s.next_after(Clock::new(0, 20)), Some(Clock::new(23, 30)));
+}
+
+const DAY: u128 = 24 * 3600 * 1_000_000_000;
+
+#[test]
+fn test_next_after_every_minute_of_the_day() {
+    let s = schedule("every 20 minutes between 09:00 and 12:00");
+    for m in 0..24 * 60 {
+        let c = Clock::new(0, m);
+        let next = s.next_after(c).unwrap();
+        assert!(s.times().contains(&next));
+        // nanoseconds from c to t, going forward
+        let ahead = |t: Clock| (t.since_midnight().as_nanos() + DAY - c.since_midnight().as_nanos()) % DAY;
+        let gap = ahead(next);
+        // nothing scheduled in between
+        assert!(s.times().iter().all(|&t| {
+            let d = ahead(t);
+            d == 0 || d >= gap
+        }), "{}", c);
+    }
+}
+
+#[test]
+fn test_iter_from_cycles_through_the_day() {
+    let s = schedule("every 6 hours");
+    let fired: Vec<String> = s.iter_from(Clock::new(13, 0)).take(5).map(|c| c.to_string()).collect();
+    assert_eq!(fired, vec!["18:00", "00:00", "06:00", "12:00", "18:00"]);
+}
+
+//
+// Days
+//
+
+#[test]
+fn test_next_after_datetime_skips_weekend() {
+    let s = schedule("every weekday at 08:30 and 17:45");
+    // friday evening -> monday morning
+    assert_eq!(s.next_after_datetime(at(8, 18, 0)), Some(at(11, 8, 30)));
+    assert_eq!(s.next_after_datetime(at(8, 17, 0)), Some(at(8, 17, 45)));
+}
+
+#[test]
+fn test_next_after_datetime_wraps_midnight_on_allowed_day() {
+    let s = schedule("every tuesday at 00:00");
+    // monday 23:59 -> tuesday 00:00
+    assert_eq!(s.next_after_datetime(at(4, 23, 59)), Some(at(5, 0, 0)));
+    // tuesday 00:00 -> next tuesday
+    assert_eq!(s.next_after_datetime(at(5, 0, 0)), Some(at(12, 0, 0)));
+}
+
+#[test]
+fn test_next_after_datetime_across_month_and_leap_day() {
+    let s = schedule("every thursday at 12:00");
+    let dt = DateTime::new(Date::new(2024, 2, 28).unwrap(), Clock::new(13, 0));
+    assert_eq!(
+        s.next_after_datetime(dt),
+        Some(DateTime::new(Date::new(2024, 2, 29).unwrap(), Clock::new(12, 0)))
+    );
+}
+
+#[test]
+fn test_iter_from_datetime_weekend_night_shift() {
+    let s = schedule("every weekend 2 hours between 22:00 and 02:00");
+    let fired: Vec<String> = s.iter_from_datetime(at(8, 23, 0)).take(4).map(|d| d.to_string()).collect();
+    // the saturday 00:00 firing belongs to saturday, friday 22:00 is not scheduled
+    assert_eq!(
+        fired,
+        vec!["2024-03-09T00:00:00", "2024-03-09T02:00:00", "2024-03-09T22:00:00", "2024-03-10T00:00:00"]
+    );
+}
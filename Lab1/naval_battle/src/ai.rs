@@ -0,0 +1,379 @@
+// Computer players. An Ai sees only what a player would see: the result of its own shots,
+// and the lengths of the enemy boats, which are known from the start.
+//
+// Strategies:
+//   random       a random square among the ones never fired at
+//   hunt         random until a hit, then the neighbours of the hit squares, along the boat
+//                once two hits are in line
+//   density      for every square, the number of ways the remaining boats could cover it;
+//                after a hit only the placements through the hit squares count. Boats of
+//                length 1 fit on every open square, so when they are many, as in the default
+//                fleet, the search for them takes most of the shots and density is no better
+//                than hunt
+//
+// hunt and density use the rule of Board::cross: boats never touch, not even diagonally,
+// so the squares around a sunk boat and the diagonal neighbours of a hit are empty.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::game::{resolve, Game, Shot};
+use crate::placement::place_fleet;
+use crate::{Board, Boat, Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    Random,
+    HuntTarget,
+    Density,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [Strategy::Random, Strategy::HuntTarget, Strategy::Density];
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // pad, so that the width in "{:>8}" is honored
+        f.pad(match self {
+            Strategy::Random => "random",
+            Strategy::HuntTarget => "hunt",
+            Strategy::Density => "density",
+        })
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Strategy::ALL.into_iter().find(|st| st.to_string() == s).ok_or_else(|| format!("unknown strategy '{}'", s))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Square {
+    Unknown,
+    Miss,
+    // hit, but the boat is still afloat
+    Hit,
+    Sunk,
+}
+
+pub struct Ai {
+    strategy: Strategy,
+    rng: StdRng,
+    width: usize,
+    height: usize,
+    // height rows of width squares
+    squares: Vec<Vec<Square>>,
+    // lengths of the boats still afloat
+    remaining: Vec<usize>,
+}
+
+fn is_orthogonal(a: (usize, usize), b: (usize, usize)) -> bool {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1) == 1
+}
+
+impl Ai {
+    // boats are the lengths of the enemy boats, on a board of the given size
+    pub fn new(strategy: Strategy, width: usize, height: usize, boats: Vec<usize>, seed: u64) -> Ai {
+        Ai {
+            strategy,
+            rng: StdRng::seed_from_u64(seed),
+            width,
+            height,
+            squares: vec![vec![Square::Unknown; width]; height],
+            remaining: boats,
+        }
+    }
+
+    // what player knows about the opponent, replaying its shots in the game
+    pub fn from_game(game: &Game, player: usize, strategy: Strategy, seed: u64) -> Ai {
+        let target = game.board(3 - player);
+        let fleet = target.fleet();
+        let mut ai = Ai::new(strategy, target.width, target.height, fleet.iter().map(|b| b.len()).collect(), seed);
+        let mut fired = HashSet::new();
+        for &(p, pos) in game.shots() {
+            if p == player {
+                fired.insert(pos);
+                ai.record(pos, resolve(&fleet, pos, |q| fired.contains(&q)));
+            }
+        }
+        ai
+    }
+
+    fn get(&self, pos: (usize, usize)) -> Square {
+        self.squares[pos.0 - 1][pos.1 - 1]
+    }
+
+    fn set(&mut self, pos: (usize, usize), sq: Square) {
+        self.squares[pos.0 - 1][pos.1 - 1] = sq;
+    }
+
+    fn all(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (1..=self.height).flat_map(move |r| (1..=width).map(move |c| (r, c)))
+    }
+
+    // the 8 neighbours of pos inside the board, origin (1,1)
+    fn around(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        let (height, width) = (self.height as isize, self.width as isize);
+        (-1..=1)
+            .flat_map(|i| (-1..=1).map(move |j| (i, j)))
+            .filter(|&d| d != (0, 0))
+            .map(move |(i, j)| (pos.0 as isize + i, pos.1 as isize + j))
+            .filter(move |&(r, c)| r >= 1 && r <= height && c >= 1 && c <= width)
+            .map(|(r, c)| (r as usize, c as usize))
+    }
+
+    pub fn record(&mut self, pos: (usize, usize), shot: Shot) {
+        match shot {
+            Shot::Miss => self.set(pos, Square::Miss),
+            Shot::Hit(_) => self.set(pos, Square::Hit),
+            Shot::Sunk(_) => {
+                // the boat is made of the hits connected to pos, boats never touch
+                let mut stack = vec![pos];
+                let mut len = 0;
+                self.set(pos, Square::Sunk);
+                while let Some(p) = stack.pop() {
+                    len += 1;
+                    for q in self.around(p).filter(|&q| is_orthogonal(p, q)) {
+                        if self.get(q) == Square::Hit {
+                            self.set(q, Square::Sunk);
+                            stack.push(q);
+                        }
+                    }
+                }
+                if let Some(i) = self.remaining.iter().position(|&l| l == len) {
+                    self.remaining.remove(i);
+                }
+            }
+        }
+    }
+
+    // false if the square can't hold a boat, by the results so far and the no-touch rule
+    fn may_hold_boat(&self, pos: (usize, usize)) -> bool {
+        self.get(pos) == Square::Unknown
+            && self.around(pos).all(|q| match self.get(q) {
+                Square::Sunk => false,
+                Square::Hit => is_orthogonal(pos, q),
+                _ => true,
+            })
+    }
+
+    // hit squares of boats still afloat
+    fn hits(&self) -> Vec<(usize, usize)> {
+        self.all().filter(|&p| self.get(p) == Square::Hit).collect()
+    }
+
+    fn pick(&mut self, candidates: Vec<(usize, usize)>) -> Option<(usize, usize)> {
+        candidates.choose(&mut self.rng).copied()
+    }
+
+    fn random(&mut self) -> Option<(usize, usize)> {
+        let unknown = self.all().filter(|&p| self.get(p) == Square::Unknown).collect();
+        self.pick(unknown)
+    }
+
+    fn hunt_target(&mut self) -> Option<(usize, usize)> {
+        let hits = self.hits();
+        let mut targets: Vec<(usize, usize)> = vec![];
+        for &h in &hits {
+            // two hits in line: the boat lies along that line
+            let in_line = hits.iter().find(|&&o| is_orthogonal(h, o));
+            for q in self.around(h).filter(|&q| is_orthogonal(h, q) && self.may_hold_boat(q)) {
+                let along = match in_line {
+                    Some(&o) => (o.0 == h.0 && q.0 == h.0) || (o.1 == h.1 && q.1 == h.1),
+                    None => true,
+                };
+                if along && !targets.contains(&q) {
+                    targets.push(q);
+                }
+            }
+        }
+        if targets.is_empty() {
+            let free = self.all().filter(|&p| self.may_hold_boat(p)).collect();
+            return self.pick(free).or_else(|| self.random());
+        }
+        self.pick(targets)
+    }
+
+    fn density(&mut self) -> Option<(usize, usize)> {
+        let hits = self.hits();
+        let mut open = vec![vec![false; self.width]; self.height];
+        for (r, c) in self.all() {
+            open[r - 1][c - 1] = self.get((r, c)) == Square::Hit || self.may_hold_boat((r, c));
+        }
+        let mut score = vec![vec![0u64; self.width]; self.height];
+        let mut lengths = self.remaining.clone();
+        lengths.sort();
+        lengths.dedup();
+        for len in lengths {
+            for start in self.all() {
+                for boat in [Boat::H(len), Boat::V(len)] {
+                    let squares = boat.squares(start);
+                    let fits = squares.iter().all(|&(r, c)| r <= self.height && c <= self.width && open[r - 1][c - 1])
+                        // a hit touching the boat from outside would belong to another boat
+                        && hits.iter().all(|h| squares.contains(h) || !squares.iter().any(|&p| p.0.abs_diff(h.0) <= 1 && p.1.abs_diff(h.1) <= 1));
+                    if !fits {
+                        continue;
+                    }
+                    let covered = squares.iter().filter(|p| hits.contains(p)).count() as u64;
+                    // once something is hit only the placements through the hits matter
+                    let weight = if hits.is_empty() { 1 } else { covered * covered };
+                    for &(r, c) in &squares {
+                        score[r - 1][c - 1] += weight;
+                    }
+                }
+            }
+        }
+        let unknown: Vec<(usize, usize)> = self.all().filter(|&p| self.get(p) == Square::Unknown).collect();
+        let best = unknown.iter().map(|&(r, c)| score[r - 1][c - 1]).max().unwrap_or(0);
+        if best == 0 {
+            return self.random();
+        }
+        let candidates = unknown.into_iter().filter(|&(r, c)| score[r - 1][c - 1] == best).collect();
+        self.pick(candidates)
+    }
+
+    // None when every square has been fired at
+    pub fn next_shot(&mut self) -> Option<(usize, usize)> {
+        match self.strategy {
+            Strategy::Random => self.random(),
+            Strategy::HuntTarget => self.hunt_target(),
+            Strategy::Density => self.density(),
+        }
+    }
+}
+
+// a board with the fleet, as in Board::new, in random places
+pub fn random_board(width: usize, height: usize, fleet: &[(usize, u8)], rng: &mut impl Rng) -> Result<Board, Error> {
+    place_fleet(Board::new(width, height, fleet), rng)
+}
+
+// shots needed by the strategy to sink every boat on the board
+pub fn play(strategy: Strategy, board: &Board, seed: u64) -> usize {
+    let fleet = board.fleet();
+    let mut ai = Ai::new(strategy, board.width, board.height, fleet.iter().map(|b| b.len()).collect(), seed);
+    let mut fired = HashSet::new();
+    let mut afloat = fleet.len();
+    while afloat > 0 {
+        let pos = ai.next_shot().expect("a boat is still afloat");
+        fired.insert(pos);
+        let shot = resolve(&fleet, pos, |q| fired.contains(&q));
+        if let Shot::Sunk(_) = shot {
+            afloat -= 1;
+        }
+        ai.record(pos, shot);
+    }
+    fired.len()
+}
+
+// average shots of every strategy over the same random boards, reproducible from the seed
+pub fn benchmark(width: usize, height: usize, fleet: &[(usize, u8)], games: usize, seed: u64) -> Result<Vec<(Strategy, f64)>, Error> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut boards = vec![];
+    for _ in 0..games {
+        boards.push((random_board(width, height, fleet, &mut rng)?, rng.gen()));
+    }
+    Ok(Strategy::ALL
+        .into_iter()
+        .map(|st| {
+            let shots: usize = boards.iter().map(|(b, s)| play(st, b, *s)).sum();
+            (st, shots as f64 / games.max(1) as f64)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLEET: [(usize, u8); 4] = [(1, 2), (2, 2), (3, 1), (4, 1)];
+
+    #[test]
+    fn strategies_from_names() {
+        for st in Strategy::ALL {
+            assert_eq!(Ok(st), st.to_string().parse());
+        }
+        assert!("smart".parse::<Strategy>().is_err());
+    }
+
+    #[test]
+    fn every_strategy_sinks_the_fleet() {
+        let board = random_board(20, 20, &FLEET, &mut StdRng::seed_from_u64(1)).unwrap();
+        assert_eq!(13, board.fleet().iter().map(|b| b.len()).sum::<usize>());
+        for st in Strategy::ALL {
+            let shots = play(st, &board, 7);
+            assert!((13..=400).contains(&shots), "{} took {} shots", st, shots);
+        }
+    }
+
+    #[test]
+    fn any_board_size() {
+        let board = random_board(9, 4, &[(2, 1), (5, 1)], &mut StdRng::seed_from_u64(2)).unwrap();
+        for st in Strategy::ALL {
+            let shots = play(st, &board, 3);
+            assert!((7..=36).contains(&shots), "{} took {} shots", st, shots);
+        }
+    }
+
+    #[test]
+    fn benchmark_is_reproducible() {
+        assert_eq!(benchmark(20, 20, &FLEET, 2, 42).unwrap(), benchmark(20, 20, &FLEET, 2, 42).unwrap());
+    }
+
+    #[test]
+    fn smarter_strategies_need_fewer_shots() {
+        let res = benchmark(20, 20, &FLEET, 5, 5).unwrap();
+        assert!(res[1].1 < res[0].1, "{:?}", res);
+        assert!(res[2].1 < res[0].1, "{:?}", res);
+        // boats of length 1 can only be found by chance, so density is ahead of hunt only
+        // without them: about 43 shots against 53 on these boards
+        let res = benchmark(10, 10, &[(2, 2), (3, 2), (4, 1), (5, 1)], 20, 5).unwrap();
+        assert!(res[2].1 + 5.0 < res[1].1, "{:?}", res);
+    }
+
+    #[test]
+    fn no_shots_around_a_sunk_boat() {
+        let mut ai = Ai::new(Strategy::HuntTarget, 20, 20, vec![2, 1], 0);
+        ai.record((5, 5), Shot::Hit(0));
+        ai.record((5, 6), Shot::Sunk(0));
+        assert_eq!(vec![1], ai.remaining);
+        for _ in 0..50 {
+            let pos = ai.next_shot().unwrap();
+            assert!(!(4..=6).contains(&pos.0) || !(4..=7).contains(&pos.1), "{:?}", pos);
+        }
+    }
+
+    #[test]
+    fn targets_follow_the_hits() {
+        for st in [Strategy::HuntTarget, Strategy::Density] {
+            let mut ai = Ai::new(st, 20, 20, vec![4], 3);
+            ai.record((10, 10), Shot::Hit(0));
+            ai.record((10, 11), Shot::Hit(0));
+            let pos = ai.next_shot().unwrap();
+            assert!(pos == (10, 9) || pos == (10, 12), "{}: {:?}", st, pos);
+        }
+    }
+
+    #[test]
+    fn knowledge_from_the_game() {
+        let b1 = Board::new(20, 20, &[(1, 1)]).add_boat(Boat::H(1), (1, 1)).unwrap();
+        let b2 = Board::new(20, 20, &[(2, 1)]).add_boat(Boat::H(2), (3, 3)).unwrap();
+        let mut game = Game::new(b1, b2).unwrap();
+        game.fire(None, (3, 3)).unwrap();
+        game.fire(None, (9, 9)).unwrap();
+        let ai = Ai::from_game(&game, 1, Strategy::Density, 0);
+        assert_eq!(Square::Hit, ai.get((3, 3)));
+        let ai = Ai::from_game(&game, 2, Strategy::Density, 0);
+        assert_eq!(Square::Miss, ai.get((9, 9)));
+        assert_eq!(Square::Unknown, ai.get((3, 3)));
+    }
+}
@@ -0,0 +1,258 @@
+// A game between two players: each one has a board with a complete fleet and they shoot in turn,
+// player 1 first, at the opponent's board. The first one to sink the whole enemy fleet wins.
+//
+// The game file holds the two boards, in the board file format, and the history of the shots:
+//
+//   player 1
+//   <board of player 1>
+//   player 2
+//   <board of player 2>
+//   shots
+//   1 3,4
+//   2 10,2
+
+use std::fmt;
+
+use crate::verify::{self, Problem};
+use crate::{split_nums, Board};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shot {
+    Miss,
+    // the index of the boat in the opponent's Board::fleet
+    Hit(usize),
+    Sunk(usize),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GameError {
+    // players are 1 and 2
+    OutOfTurn { player: usize },
+    OutOfBounds,
+    AlreadyFired,
+    GameOver { winner: usize },
+    IncompleteFleet { player: usize },
+    // the first problem found by verify::verify
+    InvalidBoard { player: usize, problem: Problem },
+    Parse(String),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::OutOfTurn { player } => write!(f, "it's player {}'s turn", player),
+            GameError::OutOfBounds => write!(f, "position out of the board"),
+            GameError::AlreadyFired => write!(f, "already fired at this position"),
+            GameError::GameOver { winner } => write!(f, "the game is over, player {} won", winner),
+            GameError::IncompleteFleet { player } => write!(f, "player {} has not placed all the boats", player),
+            GameError::InvalidBoard { player, problem } => write!(f, "invalid board of player {}: {}", player, problem),
+            GameError::Parse(e) => write!(f, "invalid game file: {}", e),
+        }
+    }
+}
+
+// the result of a shot at pos against the boats of Board::fleet,
+// given every shot fired so far at the board (pos included)
+pub fn resolve(fleet: &[Vec<(usize, usize)>], pos: (usize, usize), fired: impl Fn((usize, usize)) -> bool) -> Shot {
+    match fleet.iter().position(|boat| boat.contains(&pos)) {
+        None => Shot::Miss,
+        Some(i) if fleet[i].iter().all(|&p| fired(p)) => Shot::Sunk(i),
+        Some(i) => Shot::Hit(i),
+    }
+}
+
+pub struct Game {
+    boards: [Board; 2],
+    // (player, position) in the order they were fired
+    shots: Vec<(usize, (usize, usize))>,
+}
+
+impl Game {
+    pub fn new(board1: Board, board2: Board) -> Result<Game, GameError> {
+        for (i, b) in [&board1, &board2].into_iter().enumerate() {
+            if !b.is_complete() || b.fleet().is_empty() {
+                return Err(GameError::IncompleteFleet { player: i + 1 });
+            }
+            // a board file can break the rules of add_boat, e.g. with boats that touch
+            if let Some(problem) = verify::verify(b, None).into_iter().next() {
+                return Err(GameError::InvalidBoard { player: i + 1, problem });
+            }
+        }
+        Ok(Game { boards: [board1, board2], shots: vec![] })
+    }
+
+    pub fn from(s: &str) -> Result<Game, GameError> {
+        let err = |e: &str| GameError::Parse(e.to_string());
+        let lines: Vec<&str> = s.lines().collect();
+        let find = |header: &str| lines.iter().position(|l| l.trim_end() == header).ok_or_else(|| err(&format!("missing '{}'", header)));
+        let (p1, p2, shots) = (find("player 1")?, find("player 2")?, find("shots")?);
+        if !(p1 < p2 && p2 < shots) {
+            return Err(err("sections out of order"));
+        }
+        // the errors of a board are reported with the line in the game file
+        let board = |from: usize, to: usize| {
+            Board::from(lines[from + 1..to].join("\n")).map_err(|mut e| {
+                e.line += from + 1;
+                err(&e.to_string())
+            })
+        };
+        let mut game = Game::new(board(p1, p2)?, board(p2, shots)?)?;
+
+        for line in lines[shots + 1..].iter().filter(|l| !l.trim().is_empty()) {
+            let (player, pos) = line.trim().split_once(' ').ok_or_else(|| err(&format!("invalid shot '{}'", line)))?;
+            let player = player.parse().map_err(|_| err(&format!("invalid player '{}'", player)))?;
+            let pos = match split_nums(pos) {
+                Ok(x) if x.len() == 2 => (x[0] as usize, x[1] as usize),
+                _ => return Err(err(&format!("invalid position '{}'", pos))),
+            };
+            // the history is replayed, so an impossible one is refused
+            game.fire(Some(player), pos)?;
+        }
+        Ok(game)
+    }
+
+    pub fn board(&self, player: usize) -> &Board {
+        &self.boards[player - 1]
+    }
+
+    pub fn shots(&self) -> &[(usize, (usize, usize))] {
+        &self.shots
+    }
+
+    // the player who fires next
+    pub fn turn(&self) -> usize {
+        self.shots.len() % 2 + 1
+    }
+
+    fn fired(&self, player: usize, pos: (usize, usize)) -> bool {
+        self.shots.contains(&(player, pos))
+    }
+
+    // the player who sunk every boat of the opponent
+    pub fn winner(&self) -> Option<usize> {
+        (1..=2).find(|&p| self.boards[2 - p].fleet().iter().flatten().all(|&pos| self.fired(p, pos)))
+    }
+
+    // player None fires for whoever's turn it is
+    pub fn fire(&mut self, player: Option<usize>, pos: (usize, usize)) -> Result<Shot, GameError> {
+        if let Some(winner) = self.winner() {
+            return Err(GameError::GameOver { winner });
+        }
+        let turn = self.turn();
+        if player.is_some_and(|p| p != turn) {
+            return Err(GameError::OutOfTurn { player: turn });
+        }
+        let target = &self.boards[2 - turn];
+        if !target.in_bounds(pos) {
+            return Err(GameError::OutOfBounds);
+        }
+        if self.fired(turn, pos) {
+            return Err(GameError::AlreadyFired);
+        }
+        self.shots.push((turn, pos));
+        Ok(resolve(&self.boards[2 - turn].fleet(), pos, |p| self.fired(turn, p)))
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, board) in self.boards.iter().enumerate() {
+            writeln!(f, "player {}", i + 1)?;
+            write!(f, "{}", board)?;
+        }
+        writeln!(f, "shots")?;
+        for (player, pos) in &self.shots {
+            writeln!(f, "{} {},{}", player, pos.0, pos.1)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Boat;
+
+    // one boat of length 2 and one of length 1
+    fn board(two: (usize, usize), one: (usize, usize)) -> Board {
+        Board::new(20, 20, &[(1, 1), (2, 1)]).add_boat(Boat::H(2), two).unwrap().add_boat(Boat::H(1), one).unwrap()
+    }
+
+    fn game() -> Game {
+        Game::new(board((1, 1), (5, 5)), board((3, 3), (10, 10))).unwrap()
+    }
+
+    #[test]
+    fn hit_sunk_and_victory() {
+        let mut g = game();
+        assert_eq!(Ok(Shot::Hit(0)), g.fire(None, (3, 3)));
+        assert_eq!(Ok(Shot::Miss), g.fire(None, (2, 2)));
+        assert_eq!(Ok(Shot::Sunk(0)), g.fire(None, (3, 4)));
+        assert_eq!(Ok(Shot::Hit(0)), g.fire(None, (1, 1)));
+        assert_eq!(None, g.winner());
+        assert_eq!(Ok(Shot::Sunk(1)), g.fire(Some(1), (10, 10)));
+        assert_eq!(Some(1), g.winner());
+        assert_eq!(Err(GameError::GameOver { winner: 1 }), g.fire(Some(2), (1, 2)));
+    }
+
+    #[test]
+    fn shots_out_of_turn_are_refused() {
+        let mut g = game();
+        assert_eq!(Err(GameError::OutOfTurn { player: 1 }), g.fire(Some(2), (1, 1)));
+        g.fire(Some(1), (1, 1)).unwrap();
+        assert_eq!(2, g.turn());
+        assert_eq!(Err(GameError::OutOfTurn { player: 2 }), g.fire(Some(1), (1, 2)));
+        assert_eq!(1, g.shots().len());
+    }
+
+    #[test]
+    fn invalid_shots_are_refused() {
+        let mut g = game();
+        assert_eq!(Err(GameError::OutOfBounds), g.fire(None, (0, 3)));
+        assert_eq!(Err(GameError::OutOfBounds), g.fire(None, (21, 3)));
+        g.fire(None, (7, 7)).unwrap();
+        g.fire(None, (7, 7)).unwrap();
+        assert_eq!(Err(GameError::AlreadyFired), g.fire(None, (7, 7)));
+        assert_eq!(1, g.turn());
+    }
+
+    #[test]
+    fn fleets_must_be_complete() {
+        let partial = Board::new(20, 20, &[(1, 1), (2, 1)]).add_boat(Boat::H(2), (1, 1)).unwrap();
+        assert_eq!(Err(GameError::IncompleteFleet { player: 2 }), Game::new(board((1, 1), (5, 5)), partial).map(|_| ()));
+    }
+
+    #[test]
+    fn boards_must_follow_the_rules() {
+        let touching = Board::from("20x20 1:0 2:0\nBB\n  B".to_string()).unwrap();
+        assert_eq!(
+            Err(GameError::InvalidBoard { player: 1, problem: Problem::Touching { a: (1, 1), b: (2, 3) } }),
+            Game::new(touching, board((1, 1), (5, 5))).map(|_| ())
+        );
+        let bent = Board::from("20x20 3:0\nBB\n B".to_string()).unwrap();
+        assert!(matches!(Game::new(board((1, 1), (5, 5)), bent), Err(GameError::InvalidBoard { player: 2, .. })));
+    }
+
+    #[test]
+    fn game_file_round_trip() {
+        let mut g = game();
+        g.fire(None, (3, 3)).unwrap();
+        g.fire(None, (20, 20)).unwrap();
+        let text = g.to_string();
+        let g2 = Game::from(&text).unwrap();
+        assert_eq!(g.shots(), g2.shots());
+        assert_eq!(text, g2.to_string());
+        assert_eq!(vec![vec![(1, 1), (1, 2)], vec![(5, 5)]], g2.board(1).fleet());
+    }
+
+    #[test]
+    fn invalid_history_is_refused() {
+        let text = game().to_string() + "1 3,3\n1 3,4\n";
+        assert_eq!(Err(GameError::OutOfTurn { player: 2 }), Game::from(&text).map(|_| ()));
+        let text = game().to_string() + "1 3\n";
+        assert!(matches!(Game::from(&text), Err(GameError::Parse(_))));
+        assert!(matches!(Game::from("shots\n"), Err(GameError::Parse(_))));
+        let text = game().to_string().replace("player 2\n20x20 1:0", "player 2\n20x20 1:x");
+        assert_eq!(Err(GameError::Parse("line 24, column 7: invalid boat count '1:x', expected length:count as 3:2".to_string())), Game::from(&text).map(|_| ()));
+    }
+}
@@ -3,12 +3,21 @@
 // Clap is used with the builder pattern and subcommands, the derive based solution is commented out.
 
 
+use std::fmt;
 use std::fs;
-use std::usize;
 
-use clap::{arg, command};
+use clap::{arg, command, value_parser};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 //use clap::{Parser};
 
+mod ai;
+mod game;
+mod placement;
+mod verify;
+use ai::{Ai, Strategy};
+use game::{Game, Shot};
+
 pub mod experiments {
     use std::time::SystemTime;
 
@@ -33,7 +42,7 @@ pub mod experiments {
         for c in data_s.chars() {
             print!("{}  ", c);
         }
-        println!("");
+        println!();
         for c in data {
             print!("{:02x} ", c);
         }
@@ -124,24 +133,31 @@ pub mod experiments {
             Node { count, ..self }
         }
 
-        pub fn to_string(&self) -> String {
-            format!("{{ {} {} {} }}", self.name, self.size, self.count)
-        }
-
         pub fn grow(&mut self) {
             self.size += 1;
         }
     }
 
+    impl std::fmt::Display for Node {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "{{ {} {} {} }}", self.name, self.size, self.count)
+        }
+    }
+
     pub fn call_node() {
         let mut n = Node::new("Node").size(10).count(5);
-        println!("Node: {}", n.to_string());
+        println!("Node: {}", n);
         n.grow();
-        println!("Node: {}", n.to_string());
+        println!("Node: {}", n);
     }
 }
 
+// size of the boards whose header has only the boat counts, written before the size was in it
 const BSIZE: usize = 20;
+// largest width and height of a board
+const MAX_SIZE: usize = 100;
+// the fleet of the new boards, when not given
+const FLEET: &str = "1:6,2:4,3:3,4:2";
 
 fn split_nums(s: &str) -> Result<Vec<u8>, ()> {
     // split the boat values and convert to u8
@@ -155,10 +171,63 @@ fn split_nums(s: &str) -> Result<Vec<u8>, ()> {
     Ok(tokens)
 }
 
-#[derive(Debug)]
+// "10x8": width 10 and height 8
+fn parse_size(s: &str) -> Option<(usize, usize)> {
+    let (w, h) = s.split_once('x')?;
+    match (w.parse(), h.parse()) {
+        (Ok(w), Ok(h)) if (1..=MAX_SIZE).contains(&w) && (1..=MAX_SIZE).contains(&h) => Some((w, h)),
+        _ => None,
+    }
+}
+
+// "3:2": 2 boats of length 3
+fn parse_boats(s: &str) -> Option<(usize, u8)> {
+    let (len, n) = s.split_once(':')?;
+    match (len.parse(), n.parse()) {
+        (Ok(len), Ok(n)) if len > 0 => Some((len, n)),
+        _ => None,
+    }
+}
+
+// the fleet as "1:4,2:3,5:1", or as the number of boats of length 1, 2, 3, ...: "6,4,3,2"
+fn parse_fleet(s: &str) -> Result<Vec<(usize, u8)>, String> {
+    let invalid = || format!("Invalid boat values '{}'", s);
+    let mut fleet: Vec<(usize, u8)> = vec![];
+    if s.contains(':') {
+        for token in s.split(',') {
+            let (len, n) = parse_boats(token).ok_or_else(invalid)?;
+            if fleet.iter().any(|&(l, _)| l == len) {
+                return Err(format!("Boats of length {} given twice", len));
+            }
+            fleet.push((len, n));
+        }
+        fleet.sort();
+    } else {
+        let counts = split_nums(s).map_err(|_| invalid())?;
+        fleet = counts.into_iter().enumerate().map(|(i, n)| (i + 1, n)).collect();
+    }
+    if fleet.iter().all(|&(_, n)| n == 0) {
+        return Err(format!("No boats in '{}'", s));
+    }
+    Ok(fleet)
+}
+
+// every boat of the fleet must fit on a width x height board
+fn check_fleet(fleet: &[(usize, u8)], width: usize, height: usize) -> Result<(), String> {
+    match fleet.iter().find(|&&(len, _)| len > width.max(height)) {
+        Some((len, _)) => Err(format!("Boats of length {} don't fit on a {}x{} board", len, width, height)),
+        None => Ok(()),
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Board {
-    boats: [u8; 4],
-    data: [[u8; BSIZE]; BSIZE],
+    width: usize,
+    height: usize,
+    // (length, number of boats of that length still to place), sorted by length
+    boats: Vec<(usize, u8)>,
+    // height rows of width squares
+    data: Vec<Vec<u8>>,
 }
 
 #[derive(Debug)]
@@ -166,14 +235,86 @@ pub enum Error {
     Overlap,
     OutOfBounds,
     BoatCount,
+    // the boats to place don't fit on the board
+    NoRoom,
+    // no placement found within the tries, another seed may find one
+    GaveUp { tries: usize },
+}
+
+// where a board file is wrong, line and column start from 1
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub col: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MissingHeader,
+    InvalidSize(String),
+    InvalidCount(String),
+    DuplicateLength(usize),
+    // the number of rows and of squares allowed
+    TooManyRows(usize),
+    RowTooLong(usize),
+    InvalidSquare(char),
+    // a boat length larger than the board
+    BoatTooLong(usize),
+    // no boats to place and none on the grid
+    NoBoats,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.col)?;
+        match &self.kind {
+            ParseErrorKind::MissingHeader => write!(f, "missing the header with the boat counts"),
+            ParseErrorKind::InvalidSize(s) => write!(f, "invalid board size '{}', expected width x height as 10x10, up to {}x{}", s, MAX_SIZE, MAX_SIZE),
+            ParseErrorKind::InvalidCount(s) => write!(f, "invalid boat count '{}', expected length:count as 3:2", s),
+            ParseErrorKind::DuplicateLength(len) => write!(f, "boats of length {} given twice", len),
+            ParseErrorKind::TooManyRows(n) => write!(f, "the board has more than {} rows", n),
+            ParseErrorKind::RowTooLong(n) => write!(f, "the row is longer than {} squares", n),
+            ParseErrorKind::InvalidSquare(c) => write!(f, "invalid square {:?}, only ' ' and 'B' are allowed", c),
+            ParseErrorKind::BoatTooLong(len) => write!(f, "boats of length {} don't fit on the board", len),
+            ParseErrorKind::NoBoats => write!(f, "the board has no boats, placed or to place"),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Overlap => write!(f, "the boat overlaps or touches another boat"),
+            Error::OutOfBounds => write!(f, "the boat is out of the board"),
+            Error::BoatCount => write!(f, "no boats of this length left to place"),
+            Error::NoRoom => write!(f, "the boats left to place don't fit on the board"),
+            Error::GaveUp { tries } => write!(f, "no placement found after {} tries, try another seed", tries),
+        }
+    }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Boat {
     V(usize),
     H(usize),
 }
 
 impl Boat {
+    pub fn length(&self) -> usize {
+        match *self {
+            Boat::H(len) | Boat::V(len) => len,
+        }
+    }
+
+    // the squares covered by the boat, from start to the right (H) or downwards (V)
+    pub fn squares(&self, start: (usize, usize)) -> Vec<(usize, usize)> {
+        match *self {
+            Boat::H(len) => (0..len).map(|i| (start.0, start.1 + i)).collect(),
+            Boat::V(len) => (0..len).map(|i| (start.0 + i, start.1)).collect(),
+        }
+    }
+
     // we add a method to parse a string into a Boat (es V12 -> Boat::V(12))
     pub fn from_string(s: &str) -> Result<Boat, String> {
         let c = match s.chars().next() {
@@ -198,44 +339,99 @@ impl Boat {
 }
 
 impl Board {
-    pub fn new(boats: &[u8]) -> Board {
-        let mut b = [0; 4];
-        for i in 0..4 {
-            b[i] = boats[i];
-        }
+    // boats lists (length, count) with distinct lengths
+    pub fn new(width: usize, height: usize, boats: &[(usize, u8)]) -> Board {
+        let mut boats = boats.to_vec();
+        boats.sort();
         Board {
-            boats: b,
-            data: [[' ' as u8; BSIZE]; BSIZE],
+            width,
+            height,
+            boats,
+            data: vec![vec![b' '; width]; height],
         }
     }
 
-    pub fn from(s: String) -> Board {
-        let mut boats: [u8; 4] = [0; 4];
-        let mut data: [[u8; BSIZE]; BSIZE] = [[0; BSIZE]; BSIZE];
+    // the header line has the size and the number of boats of each length still to place,
+    // as "10x10 1:4 2:3 3:2 4:1", or in the first version only the counts of boats of length
+    // 1, 2, 3, ... on a BSIZE board, as "6 4 3 2"
+    // then the grid follows, one line per row with ' ' for water and 'B' for boats;
+    // missing squares at the end are water
+    pub fn from(s: String) -> Result<Board, ParseError> {
+        let err = |line, col, kind| Err(ParseError { line: line + 1, col: col + 1, kind });
+
+        let mut lines = s.lines().enumerate();
+        let header = lines.next().map(|(_, l)| l).unwrap_or("");
+        // the words of the header with their column
+        let mut words = vec![];
+        let mut col = 0;
+        for word in header.split(' ') {
+            if !word.is_empty() {
+                words.push((col, word));
+            }
+            col += word.chars().count() + 1;
+        }
+        if words.is_empty() {
+            return err(0, 0, ParseErrorKind::MissingHeader);
+        }
+
+        let (width, height);
+        let mut boats: Vec<(usize, u8)> = vec![];
+        if words[0].1.contains('x') {
+            (width, height) = match parse_size(words[0].1) {
+                Some(size) => size,
+                None => return err(0, 0, ParseErrorKind::InvalidSize(words[0].1.to_string())),
+            };
+            for &(col, word) in &words[1..] {
+                let (len, n) = match parse_boats(word) {
+                    Some(x) => x,
+                    None => return err(0, col, ParseErrorKind::InvalidCount(word.to_string())),
+                };
+                if boats.iter().any(|&(l, _)| l == len) {
+                    return err(0, col, ParseErrorKind::DuplicateLength(len));
+                }
+                if len > width.max(height) {
+                    return err(0, col, ParseErrorKind::BoatTooLong(len));
+                }
+                boats.push((len, n));
+            }
+        } else {
+            (width, height) = (BSIZE, BSIZE);
+            for (i, &(col, word)) in words.iter().enumerate() {
+                match word.parse() {
+                    Ok(n) if i < BSIZE => boats.push((i + 1, n)),
+                    Ok(_) => return err(0, col, ParseErrorKind::BoatTooLong(i + 1)),
+                    Err(_) => return err(0, col, ParseErrorKind::InvalidCount(word.to_string())),
+                }
+            }
+        }
 
-        let mut i = 0;
-        for line in s.lines() {
-            if i == 0 {
-                let mut j = 0;
-                for word in line.split_whitespace() {
-                    boats[j] = word.parse().unwrap();
-                    j += 1;
+        let mut board = Board::new(width, height, &boats);
+        for (i, line) in lines {
+            if i > height {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                return err(i, 0, ParseErrorKind::TooManyRows(height));
+            }
+            for (j, c) in line.chars().enumerate() {
+                if j == width {
+                    return err(i, j, ParseErrorKind::RowTooLong(width));
                 }
-            } else {
-                let mut j = 0;
-                for c in line.chars() {
-                    data[i - 1][j] = c as u8;
-                    j += 1;
+                if c != ' ' && c != 'B' {
+                    return err(i, j, ParseErrorKind::InvalidSquare(c));
                 }
+                board.data[i - 1][j] = c as u8;
             }
-            i += 1;
         }
-        Board { boats, data }
+        if board.is_complete() && board.data.iter().flatten().all(|&c| c != b'B') {
+            return err(0, 0, ParseErrorKind::NoBoats);
+        }
+        Ok(board)
     }
 
     /* true if pos in bounds */
     pub fn in_bounds(&self, pos: (usize, usize)) -> bool {
-        pos.0 >= 1 && pos.0 <= BSIZE && pos.1 >= 1 && pos.1 <= BSIZE
+        pos.0 >= 1 && pos.0 <= self.height && pos.1 >= 1 && pos.1 <= self.width
     }
 
     /* true if pos overlaps the square an existing boat, or it's an immediate neighbour  */
@@ -250,7 +446,7 @@ impl Board {
                     continue;
                 }
 
-                if self.data[x - 1][y - 1] == 'B' as u8 {
+                if self.data[x - 1][y - 1] == b'B' {
                     return true;
                 }
             }
@@ -259,67 +455,92 @@ impl Board {
         false
     }
 
-    pub fn add_boat(self, boat: Boat, start: (usize, usize)) -> Result<Board, Error> {
-        let mut new_board = self.data;
-        let mut boats = self.boats;
+    pub fn is_boat(&self, pos: (usize, usize)) -> bool {
+        self.in_bounds(pos) && self.data[pos.0 - 1][pos.1 - 1] == b'B'
+    }
 
-        let (len, squares) = match boat {
-            Boat::H(len) => {
-                let mut squares = vec![];
-                for i in 0..len {
-                    squares.push((start.0, start.1 + i));
+    // the boats on the grid: every group of connected 'B' squares is a boat,
+    // listed in reading order with its squares sorted
+    pub fn fleet(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut seen = vec![vec![false; self.width]; self.height];
+        let mut fleet = vec![];
+        for row in 1..=self.height {
+            for col in 1..=self.width {
+                if !self.is_boat((row, col)) || seen[row - 1][col - 1] {
+                    continue;
                 }
-                (len, squares)
-            }
-            Boat::V(len) => {
-                let mut squares = vec![];
-                for i in 0..len {
-                    squares.push((start.0 + i, start.1));
+                let mut boat = vec![];
+                let mut stack = vec![(row, col)];
+                seen[row - 1][col - 1] = true;
+                while let Some((r, c)) = stack.pop() {
+                    boat.push((r, c));
+                    for next in [(r - 1, c), (r + 1, c), (r, c - 1), (r, c + 1)] {
+                        if self.is_boat(next) && !seen[next.0 - 1][next.1 - 1] {
+                            seen[next.0 - 1][next.1 - 1] = true;
+                            stack.push(next);
+                        }
+                    }
                 }
-                (len, squares)
+                boat.sort();
+                fleet.push(boat);
             }
-        };
-
-        if self.boats[len - 1] == 0 {
-            return Err(Error::BoatCount);
         }
+        fleet
+    }
 
-        println!("SQUARES {:?}", squares);
+    // true when every boat of the header has been placed
+    pub fn is_complete(&self) -> bool {
+        self.boats.iter().all(|&(_, n)| n == 0)
+    }
 
-        // add a horizontal boat
-        for pos in squares {
+    pub fn add_boat(mut self, boat: Boat, start: (usize, usize)) -> Result<Board, Error> {
+        let len = boat.length();
+
+        // also a boat of length 0, or of a length that is not in the fleet
+        let i = match self.boats.iter().position(|&(l, n)| l == len && n > 0) {
+            Some(i) => i,
+            None => return Err(Error::BoatCount),
+        };
+        // checked before listing the squares, the length can be anything up to usize::MAX
+        if !self.in_bounds(start) || len > self.width.max(self.height) {
+            return Err(Error::OutOfBounds);
+        }
+        let squares = boat.squares(start);
+
+        for &pos in &squares {
             if !self.in_bounds(pos) {
-                println!("ssssss {:?}", pos);
                 return Err(Error::OutOfBounds);
             }
             if self.cross(pos) {
                 return Err(Error::Overlap);
             }
-            // it's ok to add
-            new_board[pos.0 - 1][pos.1 - 1] = 'B' as u8;
+        }
+        // it's ok to add
+        for pos in squares {
+            self.data[pos.0 - 1][pos.1 - 1] = b'B';
         }
 
-        boats[len - 1] -= 1;
-        Ok(Board {
-            boats: boats,
-            data: new_board,
-        })
+        self.boats[i].1 -= 1;
+        Ok(self)
     }
+}
 
-    pub fn to_string(&self) -> String {
-        let mut s = String::new();
-        for i in self.boats {
-            s.push_str(format!("{} ", i).as_str());
+// the file format: the boats header and then the grid
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)?;
+        for (len, n) in &self.boats {
+            write!(f, " {}:{}", len, n)?;
         }
-        s.push('\n');
+        writeln!(f)?;
 
-        for i in 0..BSIZE {
-            for j in 0..BSIZE {
-                s.push(self.data[i][j] as char);
+        for row in &self.data {
+            for &c in row {
+                write!(f, "{}", c as char)?;
             }
-            s.push('\n');
+            writeln!(f)?;
         }
-        s
+        Ok(())
     }
 }
 
@@ -332,6 +553,18 @@ impl Board {
 //    debug: bool,
 //}
 
+fn report(game: &Game, player: usize, pos: (usize, usize), shot: Shot) {
+    let at = format!("Player {} fires at {},{}", player, pos.0, pos.1);
+    match shot {
+        Shot::Miss => println!("{}: miss", at),
+        Shot::Hit(_) => println!("{}: hit", at),
+        Shot::Sunk(i) => {
+            let boat = &game.board(3 - player).fleet()[i];
+            println!("{}: sunk the boat of length {} at {},{}", at, boat.len(), boat[0].0, boat[0].1);
+        }
+    }
+}
+
 // we set a return error to the main, so that we can return a string in case of error
 fn main() -> Result<(), String> {
     // uncomment to see the individual experiments in action
@@ -366,54 +599,168 @@ fn main() -> Result<(), String> {
         .subcommand(
             command!("new")
                 .arg(arg!(<file>))
-                .arg(arg!(<boats> "number of size 1, 2, 3, 4 boats, e.g. 6,4,3,2" ))
+                .arg(arg!(--size <size> "width x height").default_value("20x20"))
+                .arg(arg!(--boats <boats> "length:count of the boats, e.g. 1:4,2:3,3:2,4:1,5:1").default_value(FLEET))
+        )
+        // place the remaining boats at random
+        .subcommand(
+            command!("auto")
+                .arg(arg!(<file>))
+                .arg(arg!(--seed <seed> "seed of the random placement").value_parser(value_parser!(u64)))
+        )
+        // check a board file against the whole fleet
+        .subcommand(
+            command!("verify")
+                .arg(arg!(<file>))
+                .arg(arg!(--boats <boats> "length:count of the boats in the whole fleet, if known"))
+        )
+        // start a game between two complete boards
+        .subcommand(
+            command!("start")
+                .arg(arg!(<game> "game file to create"))
+                .arg(arg!(<board1> "board file of player 1"))
+                .arg(arg!(<board2> "board file of player 2"))
+        )
+        // fire at the opponent, a shot out of turn is refused
+        .subcommand(
+            command!("fire")
+                .arg(arg!(<game>))
+                .arg(arg!(<pos> "target position (row, col), origin (1,1)"))
+                .arg(arg!(-p --player <player> "player firing, 1 or 2").value_parser(value_parser!(usize)).required(true))
+                .arg(arg!(--ai <strategy> "the computer answers for the other player: random, hunt or density"))
+        )
+        // the strategies of the computer playing alone on random boards
+        .subcommand(
+            command!("bench")
+                .arg(arg!(--size <size> "width x height").default_value("20x20"))
+                .arg(arg!(--boats <boats> "length:count of the boats").default_value(FLEET))
+                .arg(arg!(--games <games> "boards to play").value_parser(value_parser!(usize)).default_value("100"))
+                .arg(arg!(--seed <seed>).value_parser(value_parser!(u64)).default_value("0"))
         )
         .get_matches();
 
-    // handle subcommands add and new
+    // handle the subcommands
     match matches.subcommand() {
         Some(("add", args)) => {
             let file = args.get_one::<String>("file").unwrap();
             let boat_param = args.get_one::<String>("boat").unwrap();
             let start_pos_param = args.get_one::<String>("start_pos").unwrap();
 
-            let boat = match Boat::from_string(boat_param) {
-                Ok(x) => x,
-                Err(e) => return Err(format!("{:?}", e)),
-            };
+            let boat = Boat::from_string(boat_param)?;
 
-            let start_pos = match split_nums(&start_pos_param) {
-                Ok(x) => (x[0] as usize, x[1] as usize),
-                Err(_) => return Err("Invalid start position".to_string()),
+            let start_pos = match split_nums(start_pos_param) {
+                Ok(x) if x.len() == 2 => (x[0] as usize, x[1] as usize),
+                _ => return Err("Invalid start position".to_string()),
             };
 
-            if let Ok(data) = fs::read_to_string(file) {
-                match Board::from(data).add_boat(boat, start_pos) {
-                    Ok(board) => {
-                        fs::write(file, board.to_string()).unwrap();
-                        println!("Boat {} added at pos {}", boat_param, start_pos_param);
-                    }
-                    Err(e) => {
-                        println!(
-                            "Error adding boat {} at pos {}: {:?}",
-                            boat_param, start_pos_param, e
-                        );
-                    }
-                }
-            } else {
-                println!("Error reading file");
-            }
+            let data = fs::read_to_string(file).map_err(|e| format!("Error reading {}: {}", file, e))?;
+            let board = Board::from(data).map_err(|e| format!("Error in {}, {}", file, e))?;
+            let board = board
+                .add_boat(boat, start_pos)
+                .map_err(|e| format!("Error adding boat {} at pos {}: {}", boat_param, start_pos_param, e))?;
+            fs::write(file, board.to_string()).map_err(|e| e.to_string())?;
+            println!("Boat {} added at pos {}", boat_param, start_pos_param);
         }
         Some(("new", args)) => {
             let file = args.get_one::<String>("file").unwrap();
+            let size = args.get_one::<String>("size").unwrap();
             let boats = args.get_one::<String>("boats").unwrap();
 
-            if let Ok(tokens) = split_nums(&boats) {
-                let b = Board::new(&tokens);
-                std::fs::write(file, b.to_string()).unwrap();
-                println!("New board with boats {} written to {}", boats, file);
-            } else {
-                return Err("Invalid boat values".to_string());
+            let (width, height) = parse_size(size).ok_or(format!("Invalid size '{}', at most {}x{}", size, MAX_SIZE, MAX_SIZE))?;
+            let fleet = parse_fleet(boats)?;
+            check_fleet(&fleet, width, height)?;
+            let b = Board::new(width, height, &fleet);
+            std::fs::write(file, b.to_string()).map_err(|e| e.to_string())?;
+            println!("New {} board with boats {} written to {}", size, boats, file);
+        }
+        Some(("auto", args)) => {
+            let file = args.get_one::<String>("file").unwrap();
+            // without a seed a random one is used, and printed to place the same boats again
+            let seed = args.get_one::<u64>("seed").copied().unwrap_or_else(rand::random);
+
+            let data = fs::read_to_string(file).map_err(|e| format!("Error reading {}: {}", file, e))?;
+            let board = Board::from(data).map_err(|e| format!("Error in {}, {}", file, e))?;
+            let board = placement::place_fleet(board, &mut StdRng::seed_from_u64(seed))
+                .map_err(|e| format!("Error placing the boats in {}: {}", file, e))?;
+            fs::write(file, board.to_string()).map_err(|e| e.to_string())?;
+            println!("Boats placed in {} with seed {}", file, seed);
+        }
+        Some(("verify", args)) => {
+            let file = args.get_one::<String>("file").unwrap();
+            let fleet = match args.get_one::<String>("boats") {
+                Some(boats) => Some(parse_fleet(boats)?),
+                None => None,
+            };
+            let data = fs::read_to_string(file).map_err(|e| format!("Error reading {}: {}", file, e))?;
+            let board = Board::from(data).map_err(|e| format!("Error in {}, {}", file, e))?;
+            let problems = verify::verify(&board, fleet.as_deref());
+            for p in &problems {
+                println!("{}", p);
+            }
+            if !problems.is_empty() {
+                return Err(format!("{} problems found in {}", problems.len(), file));
+            }
+            println!("{} is consistent", file);
+        }
+        Some(("start", args)) => {
+            let file = args.get_one::<String>("game").unwrap();
+            let mut boards = vec![];
+            for name in ["board1", "board2"] {
+                let board_file = args.get_one::<String>(name).unwrap();
+                match fs::read_to_string(board_file) {
+                    Ok(data) => boards.push(Board::from(data).map_err(|e| format!("Error in {}, {}", board_file, e))?),
+                    Err(e) => return Err(format!("Error reading {}: {}", board_file, e)),
+                }
+            }
+            let board2 = boards.pop().unwrap();
+            let board1 = boards.pop().unwrap();
+            let game = Game::new(board1, board2).map_err(|e| e.to_string())?;
+            fs::write(file, game.to_string()).map_err(|e| e.to_string())?;
+            println!("New game written to {}, player 1 fires first", file);
+        }
+        Some(("fire", args)) => {
+            let file = args.get_one::<String>("game").unwrap();
+            let pos_param = args.get_one::<String>("pos").unwrap();
+            let player = *args.get_one::<usize>("player").unwrap();
+
+            let pos = match split_nums(pos_param) {
+                Ok(x) if x.len() == 2 => (x[0] as usize, x[1] as usize),
+                _ => return Err("Invalid position".to_string()),
+            };
+            let data = fs::read_to_string(file).map_err(|e| format!("Error reading {}: {}", file, e))?;
+            let mut game = Game::from(&data).map_err(|e| e.to_string())?;
+
+            let strategy = match args.get_one::<String>("ai") {
+                Some(s) => Some(s.parse::<Strategy>()?),
+                None => None,
+            };
+
+            let turn = game.turn();
+            let shot = game.fire(Some(player), pos).map_err(|e| e.to_string())?;
+            report(&game, turn, pos, shot);
+            if let (Some(strategy), None) = (strategy, game.winner()) {
+                // the seed only has to change from one shot to the next
+                let turn = game.turn();
+                let pos = Ai::from_game(&game, turn, strategy, game.shots().len() as u64).next_shot().unwrap();
+                let shot = game.fire(None, pos).map_err(|e| e.to_string())?;
+                report(&game, turn, pos, shot);
+            }
+            fs::write(file, game.to_string()).map_err(|e| e.to_string())?;
+            if let Some(winner) = game.winner() {
+                println!("Player {} wins!", winner);
+            }
+        }
+        Some(("bench", args)) => {
+            let size = args.get_one::<String>("size").unwrap();
+            let boats = args.get_one::<String>("boats").unwrap();
+            let games = *args.get_one::<usize>("games").unwrap();
+            let seed = *args.get_one::<u64>("seed").unwrap();
+
+            let (width, height) = parse_size(size).ok_or(format!("Invalid size '{}', at most {}x{}", size, MAX_SIZE, MAX_SIZE))?;
+            let fleet = parse_fleet(boats)?;
+            check_fleet(&fleet, width, height)?;
+            for (strategy, shots) in ai::benchmark(width, height, &fleet, games, seed).map_err(|e| e.to_string())? {
+                println!("{:>8}: {:.1} shots on average", strategy, shots);
             }
         }
         _ => {
@@ -425,3 +772,93 @@ fn main() -> Result<(), String> {
 
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(s: &str) -> (usize, usize, ParseErrorKind) {
+        let e = Board::from(s.to_string()).unwrap_err();
+        (e.line, e.col, e.kind)
+    }
+
+    #[test]
+    fn board_file_round_trip() {
+        let board = Board::new(12, 6, &[(1, 1), (5, 1)]).add_boat(Boat::V(5), (2, 12)).unwrap();
+        let text = board.to_string();
+        assert!(text.starts_with("12x6 1:1 5:0\n"));
+        assert_eq!(text, Board::from(text.clone()).unwrap().to_string());
+        // trailing water can be left out
+        assert_eq!(Board::new(3, 2, &[(2, 1)]).to_string(), Board::from("3x2 2:1".to_string()).unwrap().to_string());
+    }
+
+    #[test]
+    fn first_version_of_the_header() {
+        let board = Board::from("1 0 0 1 \nB".to_string()).unwrap();
+        assert_eq!((20, 20), (board.width, board.height));
+        assert_eq!(vec![(1, 1), (2, 0), (3, 0), (4, 1)], board.boats);
+        assert!(board.is_boat((1, 1)));
+    }
+
+    #[test]
+    fn board_file_errors() {
+        assert_eq!((1, 1, ParseErrorKind::MissingHeader), parse_error(""));
+        assert_eq!((1, 5, ParseErrorKind::InvalidCount("-1".to_string())), parse_error("1 2 -1 0"));
+        assert_eq!((1, 1, ParseErrorKind::InvalidSize("0x5".to_string())), parse_error("0x5 1:1"));
+        assert_eq!((1, 1, ParseErrorKind::InvalidSize("101x5".to_string())), parse_error("101x5 1:1"));
+        assert_eq!((1, 1, ParseErrorKind::InvalidSize("5x1000000000000".to_string())), parse_error("5x1000000000000 1:1"));
+        assert!(Board::from("100x100 1:1".to_string()).is_ok());
+        assert_eq!((1, 7, ParseErrorKind::InvalidCount("0:1".to_string())), parse_error("10x10 0:1"));
+        assert_eq!((1, 11, ParseErrorKind::DuplicateLength(2)), parse_error("10x10 2:1 2:3"));
+        assert_eq!((3, 4, ParseErrorKind::InvalidSquare('x')), parse_error("0 0 0 0\n\n   x"));
+        assert_eq!((2, 21, ParseErrorKind::RowTooLong(20)), parse_error(&format!("0 0 0 0\n{}", "B".repeat(21))));
+        assert_eq!((22, 1, ParseErrorKind::TooManyRows(20)), parse_error(&format!("0 0 0 0{}B", "\n".repeat(21))));
+        assert_eq!((2, 6, ParseErrorKind::RowTooLong(5)), parse_error("5x2 1:1\n     B"));
+        assert_eq!((4, 1, ParseErrorKind::TooManyRows(2)), parse_error("5x2 1:1\n\n\nB"));
+        assert!(Board::from(format!("0 0 0 1{}", "\n ".repeat(25))).is_ok());
+        assert_eq!((1, 7, ParseErrorKind::BoatTooLong(11)), parse_error("10x10 11:1"));
+        assert_eq!((1, 10, ParseErrorKind::BoatTooLong(1000000000000)), parse_error("20x3 1:1 1000000000000:1"));
+        assert_eq!((1, 41, ParseErrorKind::BoatTooLong(21)), parse_error(&"0 ".repeat(21)));
+        assert_eq!((1, 1, ParseErrorKind::NoBoats), parse_error("10x10 1:0"));
+        assert_eq!((1, 1, ParseErrorKind::NoBoats), parse_error("0 0 0 0\n"));
+        // a complete board has no boats left to place
+        assert!(Board::from("10x10 1:0\n  B".to_string()).is_ok());
+    }
+
+    #[test]
+    fn boats_of_any_length() {
+        let board = Board::new(10, 10, &[(1, 1), (5, 1)]);
+        assert!(matches!(board.clone().add_boat(Boat::H(0), (1, 1)), Err(Error::BoatCount)));
+        assert!(matches!(board.clone().add_boat(Boat::H(2), (1, 1)), Err(Error::BoatCount)));
+        assert!(matches!(board.clone().add_boat(Boat::H(5), (1, 7)), Err(Error::OutOfBounds)));
+        let board = board.add_boat(Boat::H(5), (10, 6)).unwrap();
+        assert!(matches!(board.clone().add_boat(Boat::H(5), (1, 1)), Err(Error::BoatCount)));
+        assert!(matches!(board.add_boat(Boat::V(1), (9, 5)), Err(Error::Overlap)));
+    }
+
+    #[test]
+    fn huge_boats_are_refused_without_panicking() {
+        let board = Board::new(10, 10, &[(2, 1), (usize::MAX, 1), (11, 1)]);
+        assert!(matches!(board.clone().add_boat(Boat::H(usize::MAX - 1), (1, 1)), Err(Error::BoatCount)));
+        assert!(matches!(board.clone().add_boat(Boat::H(usize::MAX), (1, 1)), Err(Error::OutOfBounds)));
+        assert!(matches!(board.clone().add_boat(Boat::V(11), (1, 1)), Err(Error::OutOfBounds)));
+        assert!(matches!(board.add_boat(Boat::H(2), (usize::MAX, usize::MAX)), Err(Error::OutOfBounds)));
+        assert_eq!(Ok(Boat::H(usize::MAX)), Boat::from_string("H18446744073709551615"));
+    }
+
+    #[test]
+    fn fleet_from_the_command_line() {
+        assert_eq!(Ok(vec![(1, 4), (2, 3), (5, 1)]), parse_fleet("5:1,1:4,2:3"));
+        assert_eq!(Ok(vec![(1, 6), (2, 4), (3, 3), (4, 2)]), parse_fleet("6,4,3,2"));
+        assert!(parse_fleet("1:4,1:2").is_err());
+        assert!(parse_fleet("1:4,2").is_err());
+        assert!(parse_fleet("0:1").is_err());
+        assert!(parse_fleet("1:0").is_err());
+        assert!(parse_fleet("0,0").is_err());
+        assert!(check_fleet(&[(1, 1), (10, 1)], 10, 4).is_ok());
+        assert!(check_fleet(&[(1, 1), (1000000000000, 1)], 20, 20).is_err());
+        assert_eq!(Some((10, 8)), parse_size("10x8"));
+        assert_eq!(None, parse_size("10"));
+        assert_eq!(Some((100, 1)), parse_size("100x1"));
+        assert_eq!(None, parse_size("100x101"));
+    }
+}
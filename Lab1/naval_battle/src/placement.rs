@@ -0,0 +1,198 @@
+// Random placement of the fleet: the boats still to place in the header of the board are put
+// on the grid by randomized backtracking, longest first. Every placement goes through
+// Board::add_boat, so it is in bounds and doesn't touch the other boats.
+//
+// Boats of the same length are interchangeable: each one is placed after the previous one
+// in the list of the placements, so the same arrangement isn't tried again in another order.
+//
+// A tight fleet is hard to place at random, so when the random search runs out of tries the
+// boats are packed instead: the squares are taken in order, row by row, and each one is either
+// the start of the next boat or left empty. A boat and the empty squares on its right and below
+// cover 2 x (len + 1) squares of the grid with one more row and column, all of them from its
+// start on and none shared with another boat, so no more squares than the fleet leaves free can
+// be skipped. This search is complete: when it fails, the fleet doesn't fit.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::{Board, Boat, Error};
+
+// placements tried at random before packing the boats
+const RANDOM_TRIES: usize = 20_000;
+// placements tried while packing before giving up
+const MAX_TRIES: usize = 200_000;
+
+// every (boat, start) of the given length on the board, in a fixed order
+fn placements(board: &Board, len: usize) -> Vec<(Boat, (usize, usize))> {
+    let mut all = vec![];
+    for row in 1..=board.height {
+        for col in 1..=board.width {
+            all.push((Boat::H(len), (row, col)));
+            if len > 1 {
+                all.push((Boat::V(len), (row, col)));
+            }
+        }
+    }
+    all
+}
+
+fn search(board: Board, lengths: &[usize], from: usize, rng: &mut impl Rng, tries: &mut usize) -> Option<Board> {
+    let (&len, rest) = match lengths.split_first() {
+        Some(x) => x,
+        None => return Some(board),
+    };
+    let all = placements(&board, len);
+    let mut order: Vec<usize> = (from..all.len()).collect();
+    order.shuffle(rng);
+    for i in order {
+        if *tries == 0 {
+            return None;
+        }
+        *tries -= 1;
+        let (boat, start) = all[i];
+        if let Ok(b) = board.clone().add_boat(boat, start) {
+            let next = if rest.first() == Some(&len) { i + 1 } else { 0 };
+            if let Some(done) = search(b, rest, next, rng, tries) {
+                return Some(done);
+            }
+        }
+    }
+    None
+}
+
+// a square of the grid with one more row and column is covered by the boat squares on it,
+// above it and on its left
+fn covered(board: &Board, (row, col): (usize, usize)) -> bool {
+    [(row, col), (row - 1, col), (row, col - 1), (row - 1, col - 1)].into_iter().any(|p| board.is_boat(p))
+}
+
+// squares of the grid with one more row and column that no boat covers
+fn free_squares(board: &Board) -> usize {
+    (1..=board.height + 1).flat_map(|row| (1..=board.width + 1).map(move |col| (row, col))).filter(|&p| !covered(board, p)).count()
+}
+
+// the next boat starts at the square from or after it; slack is the number of free squares
+// that can still be skipped
+fn pack(board: Board, from: usize, mut slack: usize, rng: &mut impl Rng, tries: &mut usize) -> Option<Board> {
+    if board.is_complete() {
+        return Some(board);
+    }
+    let width = board.width + 1;
+    for i in from..width * (board.height + 1) {
+        let start = (i / width + 1, i % width + 1);
+        if covered(&board, start) {
+            continue;
+        }
+        if board.in_bounds(start) {
+            let mut boats: Vec<Boat> = board.boats.iter().filter(|&&(_, n)| n > 0).flat_map(|&(len, _)| [Boat::H(len), Boat::V(len)]).collect();
+            boats.retain(|&b| b != Boat::V(1));
+            boats.shuffle(rng);
+            for boat in boats {
+                if *tries == 0 {
+                    return None;
+                }
+                *tries -= 1;
+                if let Ok(b) = board.clone().add_boat(boat, start) {
+                    if let Some(done) = pack(b, i + 1, slack, rng, tries) {
+                        return Some(done);
+                    }
+                }
+            }
+        }
+        // the square is left empty
+        if slack == 0 {
+            return None;
+        }
+        slack -= 1;
+    }
+    None
+}
+
+// places every boat still missing from the board
+pub fn place_fleet(board: Board, rng: &mut impl Rng) -> Result<Board, Error> {
+    let mut lengths: Vec<usize> = board.boats.iter().flat_map(|&(len, n)| std::iter::repeat_n(len, n as usize)).collect();
+    let needed: usize = lengths.iter().map(|l| 2 * (l + 1)).sum();
+    let free = free_squares(&board);
+    if needed > free {
+        return Err(Error::NoRoom);
+    }
+    lengths.reverse();
+    let mut tries = RANDOM_TRIES;
+    if let Some(done) = search(board.clone(), &lengths, 0, rng, &mut tries) {
+        return Ok(done);
+    }
+    let mut tries = MAX_TRIES;
+    match pack(board, 0, free - needed, rng, &mut tries) {
+        Some(done) => Ok(done),
+        None if tries == 0 => Err(Error::GaveUp { tries: RANDOM_TRIES + MAX_TRIES }),
+        None => Err(Error::NoRoom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const FLEET: [(usize, u8); 4] = [(1, 6), (2, 4), (3, 3), (4, 2)];
+
+    #[test]
+    fn the_whole_fleet_is_placed() {
+        let board = place_fleet(Board::new(20, 20, &FLEET), &mut StdRng::seed_from_u64(3)).unwrap();
+        assert!(board.is_complete());
+        let mut lengths: Vec<usize> = board.fleet().iter().map(|b| b.len()).collect();
+        lengths.sort();
+        assert_eq!(vec![1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4], lengths);
+        // no boat touches another one, not even diagonally
+        let fleet = board.fleet();
+        for (i, a) in fleet.iter().enumerate() {
+            for b in &fleet[i + 1..] {
+                assert!(a.iter().all(|p| b.iter().all(|q| p.0.abs_diff(q.0) > 1 || p.1.abs_diff(q.1) > 1)));
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_board() {
+        let place = |seed| place_fleet(Board::new(20, 20, &FLEET), &mut StdRng::seed_from_u64(seed)).unwrap().to_string();
+        assert_eq!(place(9), place(9));
+        assert_ne!(place(9), place(10));
+    }
+
+    #[test]
+    fn boats_already_placed_are_kept() {
+        let board = Board::new(20, 20, &[(1, 1), (4, 1)]).add_boat(Boat::V(4), (1, 1)).unwrap();
+        let board = place_fleet(board, &mut StdRng::seed_from_u64(0)).unwrap();
+        assert!(board.is_complete());
+        assert_eq!(vec![(1, 1), (2, 1), (3, 1), (4, 1)], board.fleet()[0]);
+        assert_eq!(2, board.fleet().len());
+    }
+
+    #[test]
+    fn any_size_and_boat_lengths() {
+        let board = place_fleet(Board::new(12, 7, &[(1, 2), (5, 2), (6, 1)]), &mut StdRng::seed_from_u64(1)).unwrap();
+        let mut lengths: Vec<usize> = board.fleet().iter().map(|b| b.len()).collect();
+        lengths.sort();
+        assert_eq!(vec![1, 1, 5, 5, 6], lengths);
+        assert!(board.fleet().iter().flatten().all(|&(r, c)| r <= 7 && c <= 12));
+    }
+
+    #[test]
+    fn a_fleet_too_large_is_refused() {
+        assert!(matches!(place_fleet(Board::new(20, 20, &[(4, 45)]), &mut StdRng::seed_from_u64(0)), Err(Error::NoRoom)));
+        // fits by the number of squares, but the boats can't be arranged
+        assert!(matches!(place_fleet(Board::new(4, 4, &[(3, 2), (1, 1)]), &mut StdRng::seed_from_u64(0)), Err(Error::NoRoom)));
+    }
+
+    #[test]
+    fn tight_fleets_are_packed() {
+        for seed in 0..5 {
+            let board = place_fleet(Board::new(10, 10, &[(4, 9)]), &mut StdRng::seed_from_u64(seed)).unwrap();
+            assert!(board.is_complete());
+            assert_eq!(9, board.fleet().len());
+        }
+        // one square of the grid with one more row and column is left empty
+        assert!(place_fleet(Board::new(20, 20, &[(4, 44)]), &mut StdRng::seed_from_u64(0)).unwrap().is_complete());
+    }
+}
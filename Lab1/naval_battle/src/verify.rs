@@ -0,0 +1,140 @@
+// Consistency checks of a board file. Board::from only checks the format, while here the boats
+// on the grid are compared with the header and with the rules of add_boat: every group of
+// connected 'B' squares must be a straight boat of one of the lengths in the header, boats
+// can't touch, not even diagonally, and, if the whole fleet is known, the boats placed plus
+// the ones still in the header must be the fleet.
+
+use std::fmt;
+
+use crate::Board;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Problem {
+    // the boats are named by their first square
+    NotStraight { at: (usize, usize) },
+    UnknownLength { at: (usize, usize), len: usize },
+    Touching { a: (usize, usize), b: (usize, usize) },
+    // placed and remaining boats of this length are not the fleet
+    Count { len: usize, placed: usize, remaining: usize, fleet: usize },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::NotStraight { at } => write!(f, "the boat at {},{} is not a straight line", at.0, at.1),
+            Problem::UnknownLength { at, len } => {
+                write!(f, "the boat at {},{} is {} squares long, there are no boats of this length", at.0, at.1, len)
+            }
+            Problem::Touching { a, b } => write!(f, "the boats at {},{} and {},{} touch", a.0, a.1, b.0, b.1),
+            Problem::Count { len, placed, remaining, fleet } => write!(
+                f,
+                "{} boats of length {} placed and {} to place, but the fleet has {}",
+                placed, len, remaining, fleet
+            ),
+        }
+    }
+}
+
+// every problem of the board; fleet is the number of boats of each length in the whole fleet,
+// when it is known
+pub fn verify(board: &Board, fleet: Option<&[(usize, u8)]>) -> Vec<Problem> {
+    let boats = board.fleet();
+    let mut problems = vec![];
+
+    // (length, boats of that length placed)
+    let mut placed: Vec<(usize, usize)> = board.boats.iter().map(|&(len, _)| (len, 0)).collect();
+    for boat in &boats {
+        let at = boat[0];
+        let straight = boat.iter().all(|p| p.0 == at.0) || boat.iter().all(|p| p.1 == at.1);
+        if !straight {
+            problems.push(Problem::NotStraight { at });
+            continue;
+        }
+        match placed.iter_mut().find(|(len, _)| *len == boat.len()) {
+            Some((_, n)) => *n += 1,
+            None => problems.push(Problem::UnknownLength { at, len: boat.len() }),
+        }
+    }
+
+    // connected squares are in the same boat, so boats can only touch diagonally
+    let mut owner = vec![vec![None; board.width]; board.height];
+    for (i, boat) in boats.iter().enumerate() {
+        for &(r, c) in boat {
+            owner[r - 1][c - 1] = Some(i);
+        }
+    }
+    let mut touching = vec![];
+    for (i, boat) in boats.iter().enumerate() {
+        for &(r, c) in boat {
+            for next in [(r + 1, c - 1), (r + 1, c + 1)] {
+                if !board.in_bounds(next) {
+                    continue;
+                }
+                match owner[next.0 - 1][next.1 - 1] {
+                    Some(j) if j != i && !touching.contains(&(i.min(j), i.max(j))) => touching.push((i.min(j), i.max(j))),
+                    _ => {}
+                }
+            }
+        }
+    }
+    for (i, j) in touching {
+        problems.push(Problem::Touching { a: boats[i][0], b: boats[j][0] });
+    }
+
+    if let Some(fleet) = fleet {
+        let mut lengths: Vec<usize> = board.boats.iter().chain(fleet).map(|&(len, _)| len).collect();
+        lengths.sort();
+        lengths.dedup();
+        let count = |list: &[(usize, u8)], len| list.iter().find(|&&(l, _)| l == len).map_or(0, |&(_, n)| n as usize);
+        for len in lengths {
+            let placed = placed.iter().find(|&&(l, _)| l == len).map_or(0, |&(_, n)| n);
+            let (remaining, fleet) = (count(&board.boats, len), count(fleet, len));
+            if placed + remaining != fleet {
+                problems.push(Problem::Count { len, placed, remaining, fleet });
+            }
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Boat;
+
+    fn grid(header: &str, rows: &[&str]) -> Board {
+        Board::from(format!("{}\n{}", header, rows.join("\n"))).unwrap()
+    }
+
+    #[test]
+    fn a_board_made_with_add_boat_is_consistent() {
+        let fleet = [(1, 2), (2, 1), (4, 1)];
+        let board = Board::new(20, 20, &fleet).add_boat(Boat::H(4), (1, 1)).unwrap().add_boat(Boat::V(2), (5, 5)).unwrap();
+        assert_eq!(Vec::<Problem>::new(), verify(&board, Some(&fleet)));
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let board = grid("20x20 1:1 2:0 3:0 4:0", &["BB  B", " B", "", "BBBBB", "", "   B", "    B"]);
+        let fleet = [(1, 6), (2, 4), (3, 3), (4, 2)];
+        assert_eq!(
+            vec![
+                Problem::NotStraight { at: (1, 1) },
+                Problem::UnknownLength { at: (4, 1), len: 5 },
+                Problem::Touching { a: (6, 4), b: (7, 5) },
+                Problem::Count { len: 1, placed: 3, remaining: 1, fleet: 6 },
+                Problem::Count { len: 2, placed: 0, remaining: 0, fleet: 4 },
+                Problem::Count { len: 3, placed: 0, remaining: 0, fleet: 3 },
+                Problem::Count { len: 4, placed: 0, remaining: 0, fleet: 2 },
+            ],
+            verify(&board, Some(&fleet))
+        );
+    }
+
+    #[test]
+    fn without_the_fleet_only_the_grid_is_checked() {
+        let board = grid("8x4 1:0 5:1", &["B", "", "BBBBB", "", ""]);
+        assert_eq!(Vec::<Problem>::new(), verify(&board, None));
+        assert_eq!(vec![Problem::Count { len: 5, placed: 1, remaining: 1, fleet: 1 }], verify(&board, Some(&[(1, 1), (5, 1)])));
+    }
+}
@@ -0,0 +1,149 @@
+// Movement models: a model is the set of headings a robot can face.
+// Headings are numbered clockwise, turning right/left moves to the next/previous one
+// and advancing moves by the heading's delta, so the same "A/L/R" instruction set works on every grid:
+//
+//   Direction     4 compass points, L/R turn by 90 degrees
+//   Direction8    8 neighbours with diagonal moves, L/R turn by 45 degrees
+//   HexDirection  axial (q, r) hex grid, L/R turn by 60 degrees
+
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::Direction;
+
+pub trait Heading: Copy + Eq + Debug + Hash {
+    // number of headings, must be even so that a robot can turn around
+    const COUNT: usize;
+
+    // i is taken modulo COUNT
+    fn from_index(i: usize) -> Self;
+
+    fn index(self) -> usize;
+
+    // (dx, dy) of an advance
+    fn delta(self) -> (i32, i32);
+
+    fn right(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    fn left(self) -> Self {
+        Self::from_index(self.index() + Self::COUNT - 1)
+    }
+}
+
+impl Heading for Direction {
+    const COUNT: usize = 4;
+
+    fn from_index(i: usize) -> Self {
+        Direction::from(i)
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn delta(self) -> (i32, i32) {
+        match self {
+            Direction::North => (0, 1),
+            Direction::East => (1, 0),
+            Direction::South => (0, -1),
+            Direction::West => (-1, 0),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Heading for Direction8 {
+    const COUNT: usize = 8;
+
+    fn from_index(i: usize) -> Self {
+        match i % 8 {
+            0 => Direction8::North,
+            1 => Direction8::NorthEast,
+            2 => Direction8::East,
+            3 => Direction8::SouthEast,
+            4 => Direction8::South,
+            5 => Direction8::SouthWest,
+            6 => Direction8::West,
+            7 => Direction8::NorthWest,
+            _ => unreachable!(),
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn delta(self) -> (i32, i32) {
+        match self {
+            Direction8::North => (0, 1),
+            Direction8::NorthEast => (1, 1),
+            Direction8::East => (1, 0),
+            Direction8::SouthEast => (1, -1),
+            Direction8::South => (0, -1),
+            Direction8::SouthWest => (-1, -1),
+            Direction8::West => (-1, 0),
+            Direction8::NorthWest => (-1, 1),
+        }
+    }
+}
+
+// pointy-top hexagons in axial coordinates: x is q (columns) and y is r (rows, growing southwards)
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl Heading for HexDirection {
+    const COUNT: usize = 6;
+
+    fn from_index(i: usize) -> Self {
+        match i % 6 {
+            0 => HexDirection::East,
+            1 => HexDirection::SouthEast,
+            2 => HexDirection::SouthWest,
+            3 => HexDirection::West,
+            4 => HexDirection::NorthWest,
+            5 => HexDirection::NorthEast,
+            _ => unreachable!(),
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn delta(self) -> (i32, i32) {
+        match self {
+            HexDirection::East => (1, 0),
+            HexDirection::SouthEast => (0, 1),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::West => (-1, 0),
+            HexDirection::NorthWest => (0, -1),
+            HexDirection::NorthEast => (1, -1),
+        }
+    }
+}
+
+// distance in moves between two hex cells
+pub fn hex_distance(a: (i32, i32), b: (i32, i32)) -> i32 {
+    let (dq, dr) = (a.0 - b.0, a.1 - b.1);
+    (dq.abs() + dr.abs() + (dq + dr).abs()) / 2
+}
@@ -1,9 +1,24 @@
 // The code below is a stub. Just enough to satisfy the compiler.
 // In order to pass the tests you can add-to or change any of this code.
 
-use std::process::exit;
+use std::error::Error;
+use std::fmt;
 
-#[derive(PartialEq, Eq, Debug)]
+mod heading;
+mod planner;
+mod program;
+mod recorder;
+mod simulation;
+mod world;
+
+pub use heading::{hex_distance, Direction8, Heading, HexDirection};
+pub use planner::{plan, plan_in, PlanError};
+pub use program::{Instruction, Program, SyntaxError, SyntaxErrorKind};
+pub use recorder::{Recorder, Step};
+pub use simulation::{Collision, CollisionKind, CollisionRule, Simulation, SimulationError, TickTrace};
+pub use world::{BlockPolicy, BlockReason, Blocked, SizeError, Trip, World, WorldError};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
 pub enum Direction {
     North,
     East,
@@ -11,15 +26,17 @@ pub enum Direction {
     West,
 }
 
-pub struct Robot{
+// the heading type is the movement model, see heading.rs
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Robot<D = Direction>{
     x: i32,
     y: i32,
-    d: Direction,
+    d: D,
 }
 
 impl From<usize> for Direction{
     fn from(d: usize) -> Direction {
-        match {d%4} {
+        match d%4 {
             0 => Direction::North,
             1 => Direction::East,
             2 => Direction::South,
@@ -29,53 +46,70 @@ impl From<usize> for Direction{
     }
 }
 
-impl Robot {
-    pub fn new(x: i32, y: i32, d: Direction) -> Self {
+impl<D: Heading> Robot<D> {
+    pub fn new(x: i32, y: i32, d: D) -> Self {
         Self{ x, y, d }
     }
 
     #[must_use]
     pub fn turn_right(self) -> Self {
-        Self{ d: Direction::from(self.d as usize +1), ..self}
+        Self{ d: self.d.right(), ..self}
     }
 
     #[must_use]
     pub fn turn_left(self) -> Self {
-        Self{ d: Direction::from(self.d as usize +3), ..self}
+        Self{ d: self.d.left(), ..self}
     }
 
     #[must_use]
     pub fn advance(self) -> Self {
-        match self.d {
-            Direction::North => Self{y: self.y+1, ..self },
-            Direction::East => Self{x: self.x+1, ..self },
-            Direction::South => Self{y: self.y-1, ..self },
-            Direction::West => Self{x: self.x-1, ..self },
+        let (dx, dy) = self.d.delta();
+        Self{x: self.x+dx, y: self.y+dy, ..self }
+    }
+
+    // validates the whole string before moving, so on error nothing has been executed
+    pub fn instructions(&self, instructions: &str) -> Result<Robot<D>, InstructionError> {
+        if let Some((index, found)) = instructions.chars().enumerate().find(|(_, c)| !matches!(c, 'A' | 'L' | 'R')) {
+            return Err(InstructionError { index, found });
         }
+        Ok(self.instructions_lenient(instructions).0)
     }
 
-    #[must_use]
-    pub fn instructions(self, instructions: &str) -> Self {
-        let mut r: Robot = self;
-        for inst in instructions.chars(){
+    // unknown characters are skipped and returned along with the robot
+    pub fn instructions_lenient(&self, instructions: &str) -> (Robot<D>, Vec<InstructionError>) {
+        let mut r: Robot<D> = self.clone();
+        let mut skipped = vec![];
+        for (index, inst) in instructions.chars().enumerate(){
             match inst{
                 'A' => r = r.advance(),
                 'L' => r = r.turn_left(),
                 'R' => r = r.turn_right(),
-                _ => {
-                    println!("Istruzione non valida");
-                    exit(1);
-                }
+                found => skipped.push(InstructionError { index, found }),
             };
         }
-        r
+        (r, skipped)
     }
 
     pub fn position(&self) -> (i32, i32) {
-        return (self.x, self.y)
+        (self.x, self.y)
     }
 
-    pub fn direction(&self) -> &Direction {
-        return &self.d
+    pub fn direction(&self) -> &D {
+        &self.d
     }
 }
+
+// an instruction which is not A, L or R, with its position (in chars) in the instruction string
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct InstructionError {
+    pub index: usize,
+    pub found: char,
+}
+
+impl fmt::Display for InstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid instruction '{}' at index {}", self.found, self.index)
+    }
+}
+
+impl Error for InstructionError {}
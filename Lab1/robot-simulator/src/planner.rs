@@ -0,0 +1,109 @@
+// Shortest "A/L/R" instruction string between two robot states.
+// Every instruction costs one step, so a breadth first search over (x, y, direction) states
+// finds the shortest plan.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt;
+
+use crate::{Heading, Robot, World};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum PlanError {
+    StartBlocked,
+    GoalBlocked,
+    Unreachable,
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlanError::StartBlocked => write!(f, "the robot starts on a blocked cell"),
+            PlanError::GoalBlocked => write!(f, "the goal is a blocked cell"),
+            PlanError::Unreachable => write!(f, "the goal can't be reached"),
+        }
+    }
+}
+
+impl Error for PlanError {}
+
+type State<D> = (i32, i32, D);
+
+fn state<D: Heading>(r: &Robot<D>) -> State<D> {
+    (r.x, r.y, r.d)
+}
+
+// heading None means any direction is fine at the goal
+fn bfs<D: Heading>(
+    start: &Robot<D>,
+    goal: (i32, i32),
+    heading: Option<D>,
+    free: impl Fn((i32, i32)) -> bool,
+    advance: impl Fn(&Robot<D>) -> Option<Robot<D>>,
+) -> Result<String, PlanError> {
+    if !free(start.position()) {
+        return Err(PlanError::StartBlocked);
+    }
+    if !free(goal) {
+        return Err(PlanError::GoalBlocked);
+    }
+    let is_goal = |r: &Robot<D>| r.position() == goal && heading.is_none_or(|h| h == r.d);
+
+    // for every visited state: the previous state and the instruction that led here
+    let mut prev: HashMap<State<D>, Option<(State<D>, char)>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    prev.insert(state(start), None);
+    queue.push_back(start.clone());
+
+    while let Some(r) = queue.pop_front() {
+        if is_goal(&r) {
+            let mut plan = vec![];
+            let mut s = state(&r);
+            while let Some(Some((p, inst))) = prev.get(&s) {
+                plan.push(*inst);
+                s = *p;
+            }
+            return Ok(plan.iter().rev().collect());
+        }
+        let moves = [('A', advance(&r)), ('L', Some(r.clone().turn_left())), ('R', Some(r.clone().turn_right()))];
+        for (inst, next) in moves {
+            if let Some(next) = next {
+                if let Entry::Vacant(e) = prev.entry(state(&next)) {
+                    e.insert(Some((state(&r), inst)));
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+    Err(PlanError::Unreachable)
+}
+
+// plan on the infinite plane; the search never goes more than one cell beyond
+// the box around start, goal and obstacles, where every detour fits
+pub fn plan<D: Heading>(start: &Robot<D>, goal: (i32, i32), heading: Option<D>, obstacles: &HashSet<(i32, i32)>) -> Result<String, PlanError> {
+    let ends = [start.position(), goal];
+    let cells = obstacles.iter().chain(ends.iter());
+    let min_x = cells.clone().map(|c| c.0).min().unwrap() - 1;
+    let max_x = cells.clone().map(|c| c.0).max().unwrap() + 1;
+    let min_y = cells.clone().map(|c| c.1).min().unwrap() - 1;
+    let max_y = cells.map(|c| c.1).max().unwrap() + 1;
+    let inside = |c: (i32, i32)| c.0 >= min_x && c.0 <= max_x && c.1 >= min_y && c.1 <= max_y;
+
+    bfs(
+        start,
+        goal,
+        heading,
+        |c| !obstacles.contains(&c),
+        |r| {
+            let next = r.clone().advance();
+            let c = next.position();
+            (inside(c) && !obstacles.contains(&c)).then_some(next)
+        },
+    )
+}
+
+// plan inside a World, following its policy for the edges
+pub fn plan_in<D: Heading>(world: &World, start: &Robot<D>, goal: (i32, i32), heading: Option<D>) -> Result<String, PlanError> {
+    bfs(start, goal, heading, |c| world.is_free(c), |r| world.advance(r).ok())
+}
@@ -0,0 +1,241 @@
+// A structured instruction language on top of the plain "A/L/R" strings:
+//
+//   # square of side 4
+//   4(4A R)      # a count repeats the next instruction or group
+//
+// whitespace is ignored and '#' starts a comment up to the end of the line.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Heading, Robot};
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Instruction {
+    Advance,
+    Left,
+    Right,
+    Repeat(u32, Vec<Instruction>),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Program {
+    body: Vec<Instruction>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum SyntaxErrorKind {
+    UnexpectedChar(char),
+    // a count with nothing to repeat, e.g. "3)" or "3" at the end
+    MissingInstruction,
+    UnclosedGroup,
+    UnmatchedParen,
+    CountTooLarge,
+}
+
+// line and column start from 1, columns are counted in chars
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: SyntaxErrorKind,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        match self.kind {
+            SyntaxErrorKind::UnexpectedChar(c) => write!(f, "unexpected '{}'", c),
+            SyntaxErrorKind::MissingInstruction => write!(f, "count without an instruction to repeat"),
+            SyntaxErrorKind::UnclosedGroup => write!(f, "'(' is never closed"),
+            SyntaxErrorKind::UnmatchedParen => write!(f, "')' without a matching '('"),
+            SyntaxErrorKind::CountTooLarge => write!(f, "repeat count too large"),
+        }
+    }
+}
+
+impl Error for SyntaxError {}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Parser {
+    fn error(&self, kind: SyntaxErrorKind) -> SyntaxError {
+        SyntaxError { line: self.line, column: self.column, kind }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) {
+        if self.peek() == Some('\n') {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        self.pos += 1;
+    }
+
+    // skip whitespace and comments
+    fn skip(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '#' {
+                while !matches!(self.peek(), Some('\n') | None) {
+                    self.bump();
+                }
+            } else if c.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    // instructions up to the end of the input (top level) or up to a ')' (group)
+    fn block(&mut self, group: bool) -> Result<Vec<Instruction>, SyntaxError> {
+        let mut body = vec![];
+        loop {
+            self.skip();
+            match self.peek() {
+                None if group => return Err(self.error(SyntaxErrorKind::UnclosedGroup)),
+                None => return Ok(body),
+                Some(')') if group => return Ok(body),
+                Some(')') => return Err(self.error(SyntaxErrorKind::UnmatchedParen)),
+                Some(_) => body.push(self.instruction()?),
+            }
+        }
+    }
+
+    fn instruction(&mut self) -> Result<Instruction, SyntaxError> {
+        let inst = match self.peek() {
+            Some('A') => Instruction::Advance,
+            Some('L') => Instruction::Left,
+            Some('R') => Instruction::Right,
+            Some('(') => {
+                let (line, column) = (self.line, self.column);
+                self.bump();
+                let body = self.block(true).map_err(|e| match e.kind {
+                    // report the '(' rather than the end of the input
+                    SyntaxErrorKind::UnclosedGroup => SyntaxError { line, column, ..e },
+                    _ => e,
+                })?;
+                self.bump();
+                return Ok(Instruction::Repeat(1, body));
+            }
+            Some(c) if c.is_ascii_digit() => return self.repeat(),
+            Some(c) => return Err(self.error(SyntaxErrorKind::UnexpectedChar(c))),
+            None => return Err(self.error(SyntaxErrorKind::MissingInstruction)),
+        };
+        self.bump();
+        Ok(inst)
+    }
+
+    fn repeat(&mut self) -> Result<Instruction, SyntaxError> {
+        let start = self.error(SyntaxErrorKind::CountTooLarge);
+        let mut count: u32 = 0;
+        while let Some(d) = self.peek().and_then(|c| c.to_digit(10)) {
+            count = count.checked_mul(10).and_then(|n| n.checked_add(d)).ok_or(start)?;
+            self.bump();
+        }
+        self.skip();
+        match self.peek() {
+            None | Some(')') => Err(self.error(SyntaxErrorKind::MissingInstruction)),
+            Some(c) if c.is_ascii_digit() => Err(self.error(SyntaxErrorKind::UnexpectedChar(c))),
+            _ => match self.instruction()? {
+                Instruction::Repeat(1, body) => Ok(Instruction::Repeat(count, body)),
+                inst => Ok(Instruction::Repeat(count, vec![inst])),
+            },
+        }
+    }
+}
+
+impl Program {
+    pub fn parse(s: &str) -> Result<Program, SyntaxError> {
+        let mut p = Parser { chars: s.chars().collect(), pos: 0, line: 1, column: 1 };
+        Ok(Program { body: p.block(false)? })
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.body
+    }
+
+    // the equivalent flat "A/L/R" string
+    pub fn expand(&self) -> String {
+        fn expand_block(body: &[Instruction], out: &mut String) {
+            for inst in body {
+                match inst {
+                    Instruction::Advance => out.push('A'),
+                    Instruction::Left => out.push('L'),
+                    Instruction::Right => out.push('R'),
+                    Instruction::Repeat(n, body) => {
+                        for _ in 0..*n {
+                            expand_block(body, out);
+                        }
+                    }
+                }
+            }
+        }
+        let mut out = String::new();
+        expand_block(&self.body, &mut out);
+        out
+    }
+}
+
+impl FromStr for Program {
+    type Err = SyntaxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Program::parse(s)
+    }
+}
+
+// canonical form, without comments and whitespace, every repeat in parentheses: "4(4(A)R)"
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn write_block(body: &[Instruction], f: &mut fmt::Formatter) -> fmt::Result {
+            for inst in body {
+                match inst {
+                    Instruction::Advance => write!(f, "A")?,
+                    Instruction::Left => write!(f, "L")?,
+                    Instruction::Right => write!(f, "R")?,
+                    Instruction::Repeat(n, body) => {
+                        write!(f, "{}(", n)?;
+                        write_block(body, f)?;
+                        write!(f, ")")?;
+                    }
+                }
+            }
+            Ok(())
+        }
+        write_block(&self.body, f)
+    }
+}
+
+impl<D: Heading> Robot<D> {
+    pub fn run(&self, program: &Program) -> Robot<D> {
+        fn run_block<D: Heading>(mut r: Robot<D>, body: &[Instruction]) -> Robot<D> {
+            for inst in body {
+                r = match inst {
+                    Instruction::Advance => r.advance(),
+                    Instruction::Left => r.turn_left(),
+                    Instruction::Right => r.turn_right(),
+                    Instruction::Repeat(n, body) => (0..*n).fold(r, |r, _| run_block(r, body)),
+                };
+            }
+            r
+        }
+        run_block(self.clone(), &program.body)
+    }
+
+    // parse and run in one go
+    pub fn execute(&self, source: &str) -> Result<Robot<D>, SyntaxError> {
+        Ok(self.run(&Program::parse(source)?))
+    }
+}
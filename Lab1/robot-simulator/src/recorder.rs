@@ -0,0 +1,127 @@
+// Robot methods consume self, so the intermediate states are lost:
+// the Recorder keeps every state, with undo/redo.
+
+use crate::{Direction, Heading, InstructionError, Robot};
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Step<D = Direction> {
+    pub index: usize,
+    pub instruction: char,
+    // state after the instruction
+    pub robot: Robot<D>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Recorder<D = Direction> {
+    start: Robot<D>,
+    steps: Vec<Step<D>>,
+    // undone steps, the last one is the next to redo
+    undone: Vec<Step<D>>,
+}
+
+// net effect of a run of turns, in the fewest instructions (right wins a tie)
+fn push_turns(out: &mut String, right_turns: i32, count: usize) {
+    let n = right_turns.rem_euclid(count as i32) as usize;
+    if n <= count / 2 {
+        out.push_str(&"R".repeat(n));
+    } else {
+        out.push_str(&"L".repeat(count - n));
+    }
+}
+
+impl<D: Heading> Recorder<D> {
+    pub fn new(start: Robot<D>) -> Self {
+        Recorder { start, steps: vec![], undone: vec![] }
+    }
+
+    pub fn start(&self) -> &Robot<D> {
+        &self.start
+    }
+
+    pub fn current(&self) -> &Robot<D> {
+        self.steps.last().map(|s| &s.robot).unwrap_or(&self.start)
+    }
+
+    pub fn steps(&self) -> &[Step<D>] {
+        &self.steps
+    }
+
+    fn record(&mut self, instruction: char) -> &Robot<D> {
+        let r = self.current().clone();
+        let robot = match instruction {
+            'A' => r.advance(),
+            'L' => r.turn_left(),
+            _ => r.turn_right(),
+        };
+        // a new step makes the undone ones unreachable
+        self.undone.clear();
+        self.steps.push(Step { index: self.steps.len(), instruction, robot });
+        self.current()
+    }
+
+    pub fn advance(&mut self) -> &Robot<D> {
+        self.record('A')
+    }
+
+    pub fn turn_left(&mut self) -> &Robot<D> {
+        self.record('L')
+    }
+
+    pub fn turn_right(&mut self) -> &Robot<D> {
+        self.record('R')
+    }
+
+    // as Robot::instructions, nothing is recorded if the string is invalid
+    pub fn instructions(&mut self, instructions: &str) -> Result<&Robot<D>, InstructionError> {
+        self.current().instructions(instructions)?;
+        for c in instructions.chars() {
+            self.record(c);
+        }
+        Ok(self.current())
+    }
+
+    // None if there is nothing to undo
+    pub fn undo(&mut self) -> Option<&Robot<D>> {
+        let step = self.steps.pop()?;
+        self.undone.push(step);
+        Some(self.current())
+    }
+
+    pub fn redo(&mut self) -> Option<&Robot<D>> {
+        let step = self.undone.pop()?;
+        self.steps.push(step);
+        Some(self.current())
+    }
+
+    // instructions taking the current robot back to the start, same position and direction:
+    // turn around, retrace the steps backwards swapping left and right, turn around again
+    pub fn inverse(&self) -> String {
+        let mut out = String::new();
+        let half = (D::COUNT / 2) as i32;
+        let mut turns = half;
+        for step in self.steps.iter().rev() {
+            match step.instruction {
+                'A' => {
+                    push_turns(&mut out, turns, D::COUNT);
+                    turns = 0;
+                    out.push('A');
+                }
+                'L' => turns += 1,
+                _ => turns -= 1,
+            }
+        }
+        push_turns(&mut out, turns + half, D::COUNT);
+        out
+    }
+
+    // positions visited, from the start, without the repeated ones due to turns
+    pub fn path(&self) -> Vec<(i32, i32)> {
+        let mut path = vec![self.start.position()];
+        for step in &self.steps {
+            if path.last() != Some(&step.robot.position()) {
+                path.push(step.robot.position());
+            }
+        }
+        path
+    }
+}
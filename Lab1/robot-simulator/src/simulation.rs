@@ -0,0 +1,239 @@
+// Many robots on the same plane, executing one instruction per tick.
+// All the robots move at the same time: in every tick we first compute where each robot wants to go,
+// then look for collisions (two robots in the same cell, or two robots swapping cells head-on)
+// and resolve them with the configured rule. Ids are the insertion order and make everything deterministic.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::{Direction, Heading, InstructionError, Robot};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum CollisionRule {
+    // the simulation fails at the first collision
+    #[default]
+    Error,
+    // colliding robots stay where they are and lose the instruction
+    Skip,
+    // colliding robots stay where they are and retry the instruction on the next tick
+    Wait,
+    // like Wait, but when robots compete for a free cell the lowest id gets it
+    Priority,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum CollisionKind {
+    SameCell((i32, i32)),
+    Swap,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Collision {
+    pub tick: usize,
+    pub kind: CollisionKind,
+    // ids of the robots involved, sorted
+    pub robots: Vec<usize>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum SimulationError {
+    Instruction(InstructionError),
+    Occupied((i32, i32)),
+    Collision(Collision),
+    // with Wait or Priority no robot could execute its instruction
+    Deadlock { tick: usize },
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SimulationError::Instruction(e) => write!(f, "{}", e),
+            SimulationError::Occupied(cell) => write!(f, "cell {:?} is already occupied", cell),
+            SimulationError::Collision(c) => match c.kind {
+                CollisionKind::SameCell(cell) => {
+                    write!(f, "tick {}: robots {:?} collide at {:?}", c.tick, c.robots, cell)
+                }
+                CollisionKind::Swap => write!(f, "tick {}: robots {:?} swap head-on", c.tick, c.robots),
+            },
+            SimulationError::Deadlock { tick } => write!(f, "tick {}: deadlock", tick),
+        }
+    }
+}
+
+impl Error for SimulationError {}
+
+// state of every robot (by id) at the end of a tick
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct TickTrace<D = Direction> {
+    pub tick: usize,
+    pub robots: Vec<Robot<D>>,
+    pub collisions: Vec<Collision>,
+}
+
+impl<D: Heading> TickTrace<D> {
+    pub fn positions(&self) -> Vec<(i32, i32)> {
+        self.robots.iter().map(|r| r.position()).collect()
+    }
+}
+
+struct Agent<D> {
+    robot: Robot<D>,
+    program: Vec<char>,
+    pc: usize,
+}
+
+pub struct Simulation<D = Direction> {
+    agents: Vec<Agent<D>>,
+    rule: CollisionRule,
+    tick: usize,
+    trace: Vec<TickTrace<D>>,
+}
+
+impl<D: Heading> Default for Simulation<D> {
+    fn default() -> Self {
+        Simulation::new(CollisionRule::default())
+    }
+}
+
+impl<D: Heading> Simulation<D> {
+    pub fn new(rule: CollisionRule) -> Self {
+        Simulation { agents: vec![], rule, tick: 0, trace: vec![] }
+    }
+
+    // returns the id of the new robot
+    pub fn add(&mut self, robot: Robot<D>, instructions: &str) -> Result<usize, SimulationError> {
+        robot.instructions(instructions).map_err(SimulationError::Instruction)?;
+        if self.agents.iter().any(|a| a.robot.position() == robot.position()) {
+            return Err(SimulationError::Occupied(robot.position()));
+        }
+        self.agents.push(Agent { robot, program: instructions.chars().collect(), pc: 0 });
+        Ok(self.agents.len() - 1)
+    }
+
+    pub fn robot(&self, id: usize) -> Option<&Robot<D>> {
+        self.agents.get(id).map(|a| &a.robot)
+    }
+
+    pub fn tick(&self) -> usize {
+        self.tick
+    }
+
+    pub fn trace(&self) -> &[TickTrace<D>] {
+        &self.trace
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.agents.iter().all(|a| a.pc >= a.program.len())
+    }
+
+    // one tick; Ok(false) if there was nothing left to execute
+    pub fn step(&mut self) -> Result<bool, SimulationError> {
+        if self.is_finished() {
+            return Ok(false);
+        }
+        self.tick += 1;
+        let tick = self.tick;
+
+        let current: Vec<(i32, i32)> = self.agents.iter().map(|a| a.robot.position()).collect();
+        let next: Vec<Robot<D>> = self
+            .agents
+            .iter()
+            .map(|a| match a.program.get(a.pc) {
+                Some('A') => a.robot.clone().advance(),
+                Some('L') => a.robot.clone().turn_left(),
+                Some('R') => a.robot.clone().turn_right(),
+                _ => a.robot.clone(),
+            })
+            .collect();
+        let moving: Vec<bool> = (0..next.len()).map(|i| next[i].position() != current[i]).collect();
+        let mut target: Vec<(i32, i32)> = next.iter().map(|r| r.position()).collect();
+        let mut blocked = vec![false; next.len()];
+        let mut collisions = vec![];
+        let revert = |target: &mut Vec<(i32, i32)>, blocked: &[bool]| {
+            for i in 0..target.len() {
+                if blocked[i] {
+                    target[i] = current[i];
+                }
+            }
+        };
+
+        // blocking a robot puts it back in its cell, which may cause new collisions: repeat until stable
+        loop {
+            let mut changed = false;
+            let active = |i: usize, blocked: &[bool]| moving[i] && !blocked[i];
+
+            for i in 0..target.len() {
+                for j in i + 1..target.len() {
+                    if active(i, &blocked) && active(j, &blocked) && target[i] == current[j] && target[j] == current[i] {
+                        collisions.push(Collision { tick, kind: CollisionKind::Swap, robots: vec![i, j] });
+                        blocked[i] = true;
+                        blocked[j] = true;
+                        changed = true;
+                    }
+                }
+            }
+
+            revert(&mut target, &blocked);
+
+            let mut cells: BTreeMap<(i32, i32), Vec<usize>> = BTreeMap::new();
+            for (i, &cell) in target.iter().enumerate() {
+                cells.entry(cell).or_default().push(i);
+            }
+            for (cell, ids) in cells {
+                if ids.len() < 2 {
+                    continue;
+                }
+                collisions.push(Collision { tick, kind: CollisionKind::SameCell(cell), robots: ids.clone() });
+                let movers: Vec<usize> = ids.iter().copied().filter(|&i| active(i, &blocked)).collect();
+                let someone_stays = movers.len() < ids.len();
+                // movers are sorted by id, so the first one has the priority
+                let winner = if self.rule == CollisionRule::Priority && !someone_stays { movers.first().copied() } else { None };
+                for i in movers {
+                    if Some(i) != winner {
+                        blocked[i] = true;
+                        changed = true;
+                    }
+                }
+            }
+
+            revert(&mut target, &blocked);
+            if !changed {
+                break;
+            }
+        }
+
+        if self.rule == CollisionRule::Error {
+            if let Some(c) = collisions.first() {
+                return Err(SimulationError::Collision(c.clone()));
+            }
+        }
+
+        let mut progress = false;
+        for (i, (agent, robot)) in self.agents.iter_mut().zip(next).enumerate() {
+            if agent.pc >= agent.program.len() {
+                continue;
+            }
+            if !blocked[i] {
+                agent.robot = robot;
+                agent.pc += 1;
+                progress = true;
+            } else if self.rule == CollisionRule::Skip {
+                agent.pc += 1;
+                progress = true;
+            }
+        }
+
+        self.trace.push(TickTrace { tick, robots: self.agents.iter().map(|a| a.robot.clone()).collect(), collisions });
+        if !progress {
+            return Err(SimulationError::Deadlock { tick });
+        }
+        Ok(true)
+    }
+
+    // steps until every program is done
+    pub fn run(&mut self) -> Result<&[TickTrace<D>], SimulationError> {
+        while self.step()? {}
+        Ok(&self.trace)
+    }
+}
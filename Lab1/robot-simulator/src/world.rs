@@ -0,0 +1,174 @@
+// A bounded grid with obstacles: cells go from (0, 0) to (width-1, height-1)
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+use crate::{Direction, Heading, InstructionError, Robot};
+
+// what happens when a robot advances into a blocked cell
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum BlockPolicy {
+    // the move is skipped and reported, the robot stays where it is
+    #[default]
+    Stop,
+    // the whole run fails
+    Error,
+    // leaving the grid enters it from the opposite side (torus), obstacles still stop the robot
+    Wrap,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum BlockReason {
+    Obstacle,
+    OutOfBounds,
+}
+
+// a rejected advance: index of the instruction and the cell the robot tried to enter
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Blocked {
+    pub index: usize,
+    pub cell: (i32, i32),
+    pub reason: BlockReason,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum WorldError {
+    Instruction(InstructionError),
+    Blocked(Blocked),
+}
+
+impl fmt::Display for WorldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WorldError::Instruction(e) => write!(f, "{}", e),
+            WorldError::Blocked(b) => {
+                let reason = match b.reason {
+                    BlockReason::Obstacle => "obstacle",
+                    BlockReason::OutOfBounds => "out of bounds",
+                };
+                write!(f, "instruction {} blocked at {:?}: {}", b.index, b.cell, reason)
+            }
+        }
+    }
+}
+
+impl Error for WorldError {}
+
+// a world needs at least one cell
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct SizeError {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl fmt::Display for SizeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid world size {}x{}, it must have at least one cell", self.width, self.height)
+    }
+}
+
+impl Error for SizeError {}
+
+// result of a run under the Stop or Wrap policy
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Trip<D = Direction> {
+    pub robot: Robot<D>,
+    pub blocked: Vec<Blocked>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct World {
+    width: i32,
+    height: i32,
+    obstacles: HashSet<(i32, i32)>,
+    policy: BlockPolicy,
+}
+
+impl World {
+    pub fn new(width: i32, height: i32) -> Result<Self, SizeError> {
+        if width <= 0 || height <= 0 {
+            return Err(SizeError { width, height });
+        }
+        Ok(World { width, height, obstacles: HashSet::new(), policy: BlockPolicy::default() })
+    }
+
+    pub fn obstacle(mut self, cell: (i32, i32)) -> Self {
+        self.obstacles.insert(cell);
+        self
+    }
+
+    pub fn obstacles(mut self, cells: &[(i32, i32)]) -> Self {
+        self.obstacles.extend(cells);
+        self
+    }
+
+    pub fn policy(mut self, policy: BlockPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn in_bounds(&self, cell: (i32, i32)) -> bool {
+        cell.0 >= 0 && cell.0 < self.width && cell.1 >= 0 && cell.1 < self.height
+    }
+
+    pub fn is_obstacle(&self, cell: (i32, i32)) -> bool {
+        self.obstacles.contains(&cell)
+    }
+
+    pub fn is_free(&self, cell: (i32, i32)) -> bool {
+        self.in_bounds(cell) && !self.is_obstacle(cell)
+    }
+
+    // one advance following the policy for the edges; Err if the target cell is blocked
+    pub fn advance<D: Heading>(&self, robot: &Robot<D>) -> Result<Robot<D>, Blocked> {
+        let mut next = robot.clone().advance();
+        if self.policy == BlockPolicy::Wrap {
+            next.x = next.x.rem_euclid(self.width);
+            next.y = next.y.rem_euclid(self.height);
+        }
+        let cell = next.position();
+        if !self.in_bounds(cell) {
+            return Err(Blocked { index: 0, cell, reason: BlockReason::OutOfBounds });
+        }
+        if self.is_obstacle(cell) {
+            return Err(Blocked { index: 0, cell, reason: BlockReason::Obstacle });
+        }
+        Ok(next)
+    }
+
+    // like Robot::instructions, but inside the world
+    pub fn instructions<D: Heading>(&self, robot: &Robot<D>, instructions: &str) -> Result<Trip<D>, WorldError> {
+        // validate first, as Robot::instructions does
+        robot.instructions(instructions).map_err(WorldError::Instruction)?;
+
+        let mut r = robot.clone();
+        let mut blocked = vec![];
+        for (index, inst) in instructions.chars().enumerate() {
+            r = match inst {
+                'L' => r.turn_left(),
+                'R' => r.turn_right(),
+                _ => match self.advance(&r) {
+                    Ok(next) => next,
+                    Err(b) => {
+                        let b = Blocked { index, ..b };
+                        if self.policy == BlockPolicy::Error {
+                            return Err(WorldError::Blocked(b));
+                        }
+                        blocked.push(b);
+                        r
+                    }
+                },
+            };
+        }
+        Ok(Trip { robot: r, blocked })
+    }
+}
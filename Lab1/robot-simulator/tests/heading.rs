@@ -0,0 +1,90 @@
+use std::collections::HashSet;
+
+use robot_simulator::*;
+
+#[test]
+fn compass_headings_keep_their_order() {
+    assert_eq!(4, Direction::COUNT);
+    assert_eq!(Direction::East, Direction::North.right());
+    assert_eq!(Direction::West, Direction::North.left());
+    assert_eq!((-1, 0), Direction::West.delta());
+}
+
+#[test]
+fn eight_way_turns_are_45_degrees() {
+    let r = Robot::new(0, 0, Direction8::North);
+    assert_eq!(&Direction8::NorthEast, r.clone().turn_right().direction());
+    assert_eq!(&Direction8::NorthWest, r.clone().turn_left().direction());
+    assert_eq!(&Direction8::South, r.instructions("RRRR").unwrap().direction());
+}
+
+#[test]
+fn eight_way_moves_diagonally() {
+    let r = Robot::new(0, 0, Direction8::North).instructions("RAARRA").unwrap();
+    assert_eq!((3, 1), r.position());
+    assert_eq!(&Direction8::SouthEast, r.direction());
+}
+
+#[test]
+fn hex_turns_are_60_degrees() {
+    let r = Robot::new(0, 0, HexDirection::East);
+    assert_eq!(&HexDirection::SouthEast, r.clone().turn_right().direction());
+    assert_eq!(&HexDirection::NorthEast, r.clone().turn_left().direction());
+    assert_eq!(&HexDirection::East, r.instructions("RRRRRR").unwrap().direction());
+}
+
+#[test]
+fn hex_moves_in_axial_coordinates() {
+    let r = Robot::new(0, 0, HexDirection::East).instructions("AARA").unwrap();
+    assert_eq!((2, 1), r.position());
+    assert_eq!(3, hex_distance((0, 0), r.position()));
+}
+
+#[test]
+fn hex_loop_goes_back_to_the_start() {
+    let r = Robot::new(2, -1, HexDirection::NorthWest).execute("6(2A R)").unwrap();
+    assert_eq!((2, -1), r.position());
+    assert_eq!(&HexDirection::NorthWest, r.direction());
+}
+
+#[test]
+fn hex_distance_is_symmetric() {
+    assert_eq!(0, hex_distance((1, 1), (1, 1)));
+    assert_eq!(1, hex_distance((0, 0), (1, -1)));
+    assert_eq!(2, hex_distance((0, 0), (1, 1)));
+    assert_eq!(hex_distance((3, -2), (-1, 4)), hex_distance((-1, 4), (3, -2)));
+}
+
+#[test]
+fn inverse_works_on_every_model() {
+    let mut rec = Recorder::new(Robot::new(0, 0, HexDirection::East));
+    rec.instructions("AARALLA").unwrap();
+    let back = rec.current().instructions(&rec.inverse()).unwrap();
+    assert_eq!(&back, rec.start());
+
+    let mut rec = Recorder::new(Robot::new(0, 0, Direction8::North));
+    rec.instructions("ARARRRAL").unwrap();
+    let back = rec.current().instructions(&rec.inverse()).unwrap();
+    assert_eq!(&back, rec.start());
+}
+
+#[test]
+fn eight_way_plans_use_diagonals() {
+    let start = Robot::new(0, 0, Direction8::NorthEast);
+    assert_eq!(Ok("AAA".to_string()), plan(&start, (3, 3), None, &HashSet::new()));
+}
+
+#[test]
+fn hex_plan_is_as_long_as_the_distance() {
+    let start = Robot::new(0, 0, HexDirection::East);
+    let p = plan(&start, (3, -3), None, &HashSet::new()).unwrap();
+    assert_eq!("LAAA", p);
+    assert_eq!((3, -3), start.instructions(&p).unwrap().position());
+}
+
+#[test]
+fn world_wraps_hex_robots() {
+    let world = World::new(4, 4).unwrap().policy(BlockPolicy::Wrap);
+    let trip = world.instructions(&Robot::new(3, 0, HexDirection::NorthEast), "A").unwrap();
+    assert_eq!((0, 3), trip.robot.position());
+}
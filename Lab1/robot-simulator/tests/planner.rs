@@ -0,0 +1,92 @@
+use robot_simulator::*;
+use std::collections::HashSet;
+
+fn obstacles(cells: &[(i32, i32)]) -> HashSet<(i32, i32)> {
+    cells.iter().copied().collect()
+}
+
+// the plan must be valid and actually reach the goal
+fn check(start: &Robot, plan: &str, goal: (i32, i32)) -> Robot {
+    let end = start.instructions(plan).unwrap();
+    assert_eq!(goal, end.position());
+    end
+}
+
+#[test]
+fn already_at_the_goal() {
+    let start = Robot::new(1, 1, Direction::North);
+    assert_eq!(Ok(String::new()), plan(&start, (1, 1), None, &HashSet::new()));
+}
+
+#[test]
+fn straight_line() {
+    let start = Robot::new(0, 0, Direction::North);
+    assert_eq!(Ok("AAA".to_string()), plan(&start, (0, 3), None, &HashSet::new()));
+}
+
+#[test]
+fn turn_to_the_goal_heading() {
+    let start = Robot::new(0, 0, Direction::North);
+    assert_eq!(Ok("L".to_string()), plan(&start, (0, 0), Some(Direction::West), &HashSet::new()));
+    assert_eq!(2, plan(&start, (0, 0), Some(Direction::South), &HashSet::new()).unwrap().len());
+}
+
+#[test]
+fn shortest_plan_with_turns() {
+    let start = Robot::new(0, 0, Direction::North);
+    let p = plan(&start, (3, 2), Some(Direction::East), &HashSet::new()).unwrap();
+    // 5 moves and a single turn
+    assert_eq!(6, p.len());
+    assert_eq!(&Direction::East, check(&start, &p, (3, 2)).direction());
+}
+
+#[test]
+fn go_around_a_wall() {
+    let start = Robot::new(0, 0, Direction::North);
+    let wall = obstacles(&[(-1, 2), (0, 2), (1, 2)]);
+    let p = plan(&start, (0, 4), None, &wall).unwrap();
+    check(&start, &p, (0, 4));
+    // up 1, sideways 2, up 3, back 2, plus 3 turns
+    assert_eq!(11, p.len());
+    // never steps on an obstacle
+    let mut r = start.clone();
+    for c in p.chars() {
+        r = r.instructions(&c.to_string()).unwrap();
+        assert!(!wall.contains(&r.position()));
+    }
+}
+
+#[test]
+fn enclosed_goal_is_unreachable() {
+    let start = Robot::new(0, 0, Direction::North);
+    let ring = obstacles(&[(4, 5), (6, 5), (5, 4), (5, 6)]);
+    assert_eq!(Err(PlanError::Unreachable), plan(&start, (5, 5), None, &ring));
+}
+
+#[test]
+fn blocked_start_or_goal() {
+    let start = Robot::new(0, 0, Direction::North);
+    assert_eq!(Err(PlanError::GoalBlocked), plan(&start, (2, 2), None, &obstacles(&[(2, 2)])));
+    assert_eq!(Err(PlanError::StartBlocked), plan(&start, (2, 2), None, &obstacles(&[(0, 0)])));
+}
+
+#[test]
+fn plan_inside_a_world() {
+    // corridor with a single gap at (2, 3)
+    let world = World::new(5, 5).unwrap().obstacles(&[(2, 0), (2, 1), (2, 2), (2, 4)]);
+    let start = Robot::new(0, 0, Direction::East);
+    let p = plan_in(&world, &start, (4, 0), None).unwrap();
+    let trip = world.instructions(&start, &p).unwrap();
+    assert_eq!((4, 0), trip.robot.position());
+    assert!(trip.blocked.is_empty());
+}
+
+#[test]
+fn plan_uses_the_torus() {
+    let world = World::new(10, 1).unwrap().policy(BlockPolicy::Wrap);
+    let start = Robot::new(0, 0, Direction::East);
+    // going west through the edge is shorter than 8 steps east
+    let p = plan_in(&world, &start, (8, 0), None).unwrap();
+    assert_eq!(4, p.len());
+    assert_eq!((8, 0), world.instructions(&start, &p).unwrap().robot.position());
+}
@@ -0,0 +1,120 @@
+use robot_simulator::*;
+
+fn syntax_error(line: usize, column: usize, kind: SyntaxErrorKind) -> SyntaxError {
+    SyntaxError { line, column, kind }
+}
+
+#[test]
+fn plain_strings_are_programs() {
+    let program = Program::parse("LAAARALA").unwrap();
+    assert_eq!("LAAARALA", program.expand());
+    let robot = Robot::new(0, 0, Direction::North).run(&program);
+    assert_eq!((-4, 1), robot.position());
+    assert_eq!(&Direction::West, robot.direction());
+}
+
+#[test]
+fn repeat_count() {
+    let program = Program::parse("4A").unwrap();
+    assert_eq!(&[Instruction::Repeat(4, vec![Instruction::Advance])], program.instructions());
+    assert_eq!("AAAA", program.expand());
+}
+
+#[test]
+fn repeated_group() {
+    let program = Program::parse("3(AL)").unwrap();
+    assert_eq!(
+        &[Instruction::Repeat(3, vec![Instruction::Advance, Instruction::Left])],
+        program.instructions()
+    );
+    assert_eq!("ALALAL", program.expand());
+}
+
+#[test]
+fn nested_groups() {
+    assert_eq!("AARAARAAR", Program::parse("3(2A R)").unwrap().expand());
+    assert_eq!("ALALARALALAR", Program::parse("2(2(AL) AR)").unwrap().expand());
+}
+
+#[test]
+fn zero_count_does_nothing() {
+    assert_eq!("R", Program::parse("0(AAA) R").unwrap().expand());
+}
+
+#[test]
+fn comments_and_whitespace_are_ignored() {
+    let source = "# a square\n4( 4A # side\n   R )\n\n# done";
+    let robot = Robot::new(0, 0, Direction::North).execute(source).unwrap();
+    assert_eq!((0, 0), robot.position());
+    assert_eq!(&Direction::North, robot.direction());
+}
+
+#[test]
+fn same_result_as_expanded_instructions() {
+    let robot = Robot::new(3, -2, Direction::East);
+    let program = Program::parse("2(3A L) 10R 2(A(RA))").unwrap();
+    assert_eq!(robot.instructions(&program.expand()).unwrap(), robot.run(&program));
+}
+
+#[test]
+fn display_is_canonical() {
+    assert_eq!("4(A)3(AL)R", Program::parse(" 4A 3 ( A L ) # x\nR").unwrap().to_string());
+}
+
+#[test]
+fn unexpected_char_position() {
+    assert_eq!(
+        Err(syntax_error(2, 3, SyntaxErrorKind::UnexpectedChar('x'))),
+        Program::parse("AA\nAAxA")
+    );
+}
+
+#[test]
+fn non_ascii_comments_do_not_shift_positions() {
+    assert_eq!(
+        Err(syntax_error(2, 3, SyntaxErrorKind::UnexpectedChar('é'))),
+        Program::parse("A # ééé\nA é")
+    );
+}
+
+#[test]
+fn unclosed_group_points_to_the_open_paren() {
+    assert_eq!(
+        Err(syntax_error(1, 4, SyntaxErrorKind::UnclosedGroup)),
+        Program::parse("A 2(AL\nA")
+    );
+}
+
+#[test]
+fn unmatched_close_paren() {
+    assert_eq!(
+        Err(syntax_error(1, 3, SyntaxErrorKind::UnmatchedParen)),
+        Program::parse("AA)")
+    );
+}
+
+#[test]
+fn count_without_instruction() {
+    assert_eq!(
+        Err(syntax_error(1, 4, SyntaxErrorKind::MissingInstruction)),
+        Program::parse("A 3")
+    );
+    assert_eq!(
+        Err(syntax_error(1, 5, SyntaxErrorKind::MissingInstruction)),
+        Program::parse("(A 3)")
+    );
+}
+
+#[test]
+fn count_too_large() {
+    assert_eq!(
+        Err(syntax_error(1, 2, SyntaxErrorKind::CountTooLarge)),
+        Program::parse("A99999999999A")
+    );
+}
+
+#[test]
+fn syntax_error_message() {
+    let err = Program::parse("A\n(A").unwrap_err();
+    assert_eq!("2:1: '(' is never closed", err.to_string());
+}
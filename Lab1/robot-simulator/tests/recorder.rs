@@ -0,0 +1,100 @@
+use robot_simulator::*;
+
+#[test]
+fn every_state_is_recorded() {
+    let mut rec = Recorder::new(Robot::new(0, 0, Direction::North));
+    rec.advance();
+    rec.turn_right();
+    rec.advance();
+    assert_eq!(
+        vec![
+            Step { index: 0, instruction: 'A', robot: Robot::new(0, 1, Direction::North) },
+            Step { index: 1, instruction: 'R', robot: Robot::new(0, 1, Direction::East) },
+            Step { index: 2, instruction: 'A', robot: Robot::new(1, 1, Direction::East) },
+        ],
+        rec.steps()
+    );
+    assert_eq!(&Robot::new(1, 1, Direction::East), rec.current());
+}
+
+#[test]
+fn recorded_instructions_match_the_plain_robot() {
+    let start = Robot::new(8, 4, Direction::South);
+    let mut rec = Recorder::new(start.clone());
+    let end = rec.instructions("LAAARRRALLLL").unwrap().clone();
+    assert_eq!(start.instructions("LAAARRRALLLL").unwrap(), end);
+    assert_eq!(12, rec.steps().len());
+}
+
+#[test]
+fn invalid_instructions_record_nothing() {
+    let mut rec = Recorder::new(Robot::new(0, 0, Direction::North));
+    rec.advance();
+    assert_eq!(Err(InstructionError { index: 2, found: 'x' }), rec.instructions("AAx").cloned());
+    assert_eq!(1, rec.steps().len());
+}
+
+#[test]
+fn undo_and_redo() {
+    let mut rec = Recorder::new(Robot::new(0, 0, Direction::North));
+    rec.instructions("AAR").unwrap();
+    assert_eq!(Some(&Robot::new(0, 2, Direction::North)), rec.undo());
+    assert_eq!(Some(&Robot::new(0, 1, Direction::North)), rec.undo());
+    assert_eq!(Some(&Robot::new(0, 2, Direction::North)), rec.redo());
+    assert_eq!(Some(&Robot::new(0, 2, Direction::East)), rec.redo());
+    assert_eq!(None, rec.redo());
+}
+
+#[test]
+fn undo_everything() {
+    let mut rec = Recorder::new(Robot::new(3, 3, Direction::West));
+    rec.instructions("AL").unwrap();
+    rec.undo();
+    rec.undo();
+    assert_eq!(None, rec.undo());
+    assert_eq!(rec.start(), rec.current());
+}
+
+#[test]
+fn new_step_clears_redo() {
+    let mut rec = Recorder::new(Robot::new(0, 0, Direction::North));
+    rec.instructions("AA").unwrap();
+    rec.undo();
+    rec.turn_left();
+    assert_eq!(None, rec.redo());
+    assert_eq!(vec![0, 1], rec.steps().iter().map(|s| s.index).collect::<Vec<_>>());
+}
+
+#[test]
+fn inverse_returns_to_the_start() {
+    for program in ["", "A", "L", "RR", "AAR", "LAAARALA", "RRAAAAALA", "AALAARAAAL", "ALALALAL"] {
+        let start = Robot::new(2, -7, Direction::East);
+        let mut rec = Recorder::new(start.clone());
+        rec.instructions(program).unwrap();
+        let back = rec.current().instructions(&rec.inverse()).unwrap();
+        assert_eq!(start, back, "{}", program);
+    }
+}
+
+#[test]
+fn inverse_is_minimal_for_turns() {
+    let mut rec = Recorder::new(Robot::new(0, 0, Direction::North));
+    rec.instructions("RRR").unwrap();
+    assert_eq!("R", rec.inverse());
+    rec.instructions("R").unwrap();
+    assert_eq!("", rec.inverse());
+}
+
+#[test]
+fn inverse_of_a_straight_line() {
+    let mut rec = Recorder::new(Robot::new(0, 0, Direction::North));
+    rec.instructions("AAA").unwrap();
+    assert_eq!("RRAAARR", rec.inverse());
+}
+
+#[test]
+fn path_for_plotting() {
+    let mut rec = Recorder::new(Robot::new(0, 0, Direction::North));
+    rec.instructions("AARAL").unwrap();
+    assert_eq!(vec![(0, 0), (0, 1), (0, 2), (1, 2)], rec.path());
+}
@@ -106,21 +106,58 @@ fn advance_decreases_the_x_coordinate_by_one_when_facing_west() {
 
 #[test]
 fn follow_instructions_to_move_west_and_north() {
-    let robot = Robot::new(0, 0, Direction::North).instructions("LAAARALA");
+    let robot = Robot::new(0, 0, Direction::North).instructions("LAAARALA").unwrap();
     assert_eq!((-4, 1), robot.position());
     assert_eq!(&Direction::West, robot.direction());
 }
 
 #[test]
 fn follow_instructions_to_move_west_and_south() {
-    let robot = Robot::new(2, -7, Direction::East).instructions("RRAAAAALA");
+    let robot = Robot::new(2, -7, Direction::East).instructions("RRAAAAALA").unwrap();
     assert_eq!((-3, -8), robot.position());
     assert_eq!(&Direction::South, robot.direction());
 }
 
 #[test]
 fn follow_instructions_to_move_east_and_north() {
-    let robot = Robot::new(8, 4, Direction::South).instructions("LAAARRRALLLL");
+    let robot = Robot::new(8, 4, Direction::South).instructions("LAAARRRALLLL").unwrap();
     assert_eq!((11, 5), robot.position());
     assert_eq!(&Direction::North, robot.direction());
 }
+
+#[test]
+fn invalid_instruction_is_reported_with_its_index() {
+    let robot = Robot::new(0, 0, Direction::North);
+    let err = robot.instructions("AALXA").unwrap_err();
+    assert_eq!(InstructionError { index: 3, found: 'X' }, err);
+    assert_eq!("invalid instruction 'X' at index 3", err.to_string());
+}
+
+#[test]
+fn invalid_instruction_leaves_the_robot_unchanged() {
+    let robot = Robot::new(2, 3, Direction::East);
+    assert!(robot.instructions("AAAAx").is_err());
+    assert_eq!((2, 3), robot.position());
+    assert_eq!(&Direction::East, robot.direction());
+}
+
+#[test]
+fn index_counts_chars_not_bytes() {
+    let err = Robot::new(0, 0, Direction::North).instructions("éA?").unwrap_err();
+    assert_eq!(InstructionError { index: 0, found: 'é' }, err);
+}
+
+#[test]
+fn lenient_instructions_skip_unknown_characters() {
+    let (robot, skipped) = Robot::new(0, 0, Direction::North).instructions_lenient("A A-R?A");
+    assert_eq!((1, 2), robot.position());
+    assert_eq!(&Direction::East, robot.direction());
+    assert_eq!(
+        vec![
+            InstructionError { index: 1, found: ' ' },
+            InstructionError { index: 3, found: '-' },
+            InstructionError { index: 5, found: '?' },
+        ],
+        skipped
+    );
+}
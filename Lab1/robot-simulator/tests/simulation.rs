@@ -0,0 +1,158 @@
+use robot_simulator::*;
+
+fn positions(sim: &Simulation) -> Vec<Vec<(i32, i32)>> {
+    sim.trace().iter().map(|t| t.positions()).collect()
+}
+
+#[test]
+fn robots_without_conflicts_run_their_programs() {
+    let mut sim = Simulation::new(CollisionRule::Error);
+    sim.add(Robot::new(0, 0, Direction::North), "AA").unwrap();
+    sim.add(Robot::new(5, 5, Direction::East), "RA").unwrap();
+    sim.run().unwrap();
+    assert_eq!(vec![vec![(0, 1), (5, 5)], vec![(0, 2), (5, 4)]], positions(&sim));
+    assert_eq!(&Direction::South, sim.robot(1).unwrap().direction());
+    assert!(sim.is_finished());
+}
+
+#[test]
+fn one_instruction_per_tick_until_the_longest_program_ends() {
+    let mut sim = Simulation::new(CollisionRule::Error);
+    sim.add(Robot::new(0, 0, Direction::North), "A").unwrap();
+    sim.add(Robot::new(9, 9, Direction::North), "LLLA").unwrap();
+    assert_eq!(4, sim.run().unwrap().len());
+    assert_eq!((0, 1), sim.robot(0).unwrap().position());
+    assert_eq!((10, 9), sim.robot(1).unwrap().position());
+    assert!(!sim.step().unwrap());
+}
+
+#[test]
+fn invalid_program_and_occupied_cell_are_rejected() {
+    let mut sim = Simulation::new(CollisionRule::Error);
+    assert_eq!(
+        Err(SimulationError::Instruction(InstructionError { index: 1, found: 'B' })),
+        sim.add(Robot::new(0, 0, Direction::North), "AB")
+    );
+    sim.add(Robot::new(0, 0, Direction::North), "A").unwrap();
+    assert_eq!(
+        Err(SimulationError::Occupied((0, 0))),
+        sim.add(Robot::new(0, 0, Direction::South), "A")
+    );
+}
+
+#[test]
+fn same_cell_collision_is_an_error() {
+    let mut sim = Simulation::new(CollisionRule::Error);
+    sim.add(Robot::new(0, 0, Direction::East), "A").unwrap();
+    sim.add(Robot::new(2, 0, Direction::West), "A").unwrap();
+    let err = sim.run().unwrap_err();
+    assert_eq!(
+        SimulationError::Collision(Collision { tick: 1, kind: CollisionKind::SameCell((1, 0)), robots: vec![0, 1] }),
+        err
+    );
+    assert_eq!("tick 1: robots [0, 1] collide at (1, 0)", err.to_string());
+}
+
+#[test]
+fn head_on_swap_is_detected() {
+    let mut sim = Simulation::new(CollisionRule::Error);
+    sim.add(Robot::new(0, 0, Direction::East), "A").unwrap();
+    sim.add(Robot::new(1, 0, Direction::West), "A").unwrap();
+    assert_eq!(
+        Err(SimulationError::Collision(Collision { tick: 1, kind: CollisionKind::Swap, robots: vec![0, 1] })),
+        sim.run().map(|_| ())
+    );
+}
+
+#[test]
+fn following_in_a_line_is_not_a_collision() {
+    let mut sim = Simulation::new(CollisionRule::Error);
+    sim.add(Robot::new(0, 0, Direction::East), "AAA").unwrap();
+    sim.add(Robot::new(1, 0, Direction::East), "AAA").unwrap();
+    sim.run().unwrap();
+    assert_eq!((3, 0), sim.robot(0).unwrap().position());
+    assert_eq!((4, 0), sim.robot(1).unwrap().position());
+}
+
+#[test]
+fn moving_into_a_robot_that_stays_is_a_collision() {
+    let mut sim = Simulation::new(CollisionRule::Error);
+    sim.add(Robot::new(0, 0, Direction::East), "A").unwrap();
+    sim.add(Robot::new(1, 0, Direction::North), "L").unwrap();
+    assert!(matches!(sim.run(), Err(SimulationError::Collision(Collision { kind: CollisionKind::SameCell((1, 0)), .. }))));
+}
+
+#[test]
+fn skip_drops_colliding_moves() {
+    let mut sim = Simulation::new(CollisionRule::Skip);
+    sim.add(Robot::new(0, 0, Direction::East), "AA").unwrap();
+    sim.add(Robot::new(2, 0, Direction::West), "AR").unwrap();
+    sim.run().unwrap();
+    assert_eq!(vec![vec![(0, 0), (2, 0)], vec![(1, 0), (2, 0)]], positions(&sim));
+    assert_eq!(1, sim.trace()[0].collisions.len());
+    assert!(sim.trace()[1].collisions.is_empty());
+}
+
+#[test]
+fn wait_retries_the_move_on_the_next_tick() {
+    let mut sim = Simulation::new(CollisionRule::Wait);
+    // robot 1 leaves the cell robot 0 wants only after turning
+    sim.add(Robot::new(0, 0, Direction::East), "A").unwrap();
+    sim.add(Robot::new(1, 0, Direction::East), "LA").unwrap();
+    sim.run().unwrap();
+    assert_eq!(
+        vec![vec![(0, 0), (1, 0)], vec![(1, 0), (1, 1)]],
+        positions(&sim)
+    );
+}
+
+#[test]
+fn blocking_a_robot_propagates_down_the_line() {
+    let mut sim = Simulation::new(CollisionRule::Wait);
+    // 0 follows 1, 1 hits 2 which turns: both 0 and 1 must wait
+    sim.add(Robot::new(0, 0, Direction::East), "A").unwrap();
+    sim.add(Robot::new(1, 0, Direction::East), "A").unwrap();
+    sim.add(Robot::new(2, 0, Direction::North), "L").unwrap();
+    sim.step().unwrap();
+    let trace = &sim.trace()[0];
+    assert_eq!(vec![(0, 0), (1, 0), (2, 0)], trace.positions());
+    assert_eq!(2, trace.collisions.len());
+    sim.run().unwrap_err();
+}
+
+#[test]
+fn wait_with_a_swap_is_a_deadlock() {
+    let mut sim = Simulation::new(CollisionRule::Wait);
+    sim.add(Robot::new(0, 0, Direction::East), "A").unwrap();
+    sim.add(Robot::new(1, 0, Direction::West), "A").unwrap();
+    assert_eq!(Err(SimulationError::Deadlock { tick: 1 }), sim.run().map(|_| ()));
+}
+
+#[test]
+fn priority_lets_the_lowest_id_through() {
+    let mut sim = Simulation::new(CollisionRule::Priority);
+    sim.add(Robot::new(2, 0, Direction::West), "ARA").unwrap();
+    sim.add(Robot::new(0, 0, Direction::East), "AA").unwrap();
+    sim.run().unwrap();
+    assert_eq!(
+        vec![
+            vec![(1, 0), (0, 0)],
+            vec![(1, 0), (0, 0)],
+            vec![(1, 1), (1, 0)],
+            vec![(1, 1), (2, 0)],
+        ],
+        positions(&sim)
+    );
+}
+
+#[test]
+fn trace_is_deterministic() {
+    let build = || {
+        let mut sim = Simulation::new(CollisionRule::Skip);
+        sim.add(Robot::new(0, 0, Direction::East), "AALAA").unwrap();
+        sim.add(Robot::new(2, 2, Direction::South), "AARAA").unwrap();
+        sim.add(Robot::new(4, 0, Direction::West), "AAAAA").unwrap();
+        sim.run().unwrap().to_vec()
+    };
+    assert_eq!(build(), build());
+}
@@ -0,0 +1,100 @@
+use robot_simulator::*;
+
+fn warehouse(policy: BlockPolicy) -> World {
+    // 5x5 floor with a shelf at (2, 1) and (2, 2)
+    World::new(5, 5).unwrap().obstacles(&[(2, 1), (2, 2)]).policy(policy)
+}
+
+#[test]
+fn empty_world_is_refused() {
+    assert_eq!(World::new(0, 3), Err(SizeError { width: 0, height: 3 }));
+    assert_eq!(World::new(3, -1), Err(SizeError { width: 3, height: -1 }));
+    assert!(World::new(1, 1).is_ok());
+}
+
+#[test]
+fn free_cells() {
+    let world = warehouse(BlockPolicy::Stop);
+    assert!(world.is_free((0, 0)));
+    assert!(world.is_free((4, 4)));
+    assert!(!world.is_free((2, 2)));
+    assert!(!world.is_free((5, 0)));
+    assert!(!world.is_free((0, -1)));
+}
+
+#[test]
+fn moves_like_the_plain_robot_when_nothing_is_in_the_way() {
+    let robot = Robot::new(0, 0, Direction::North);
+    let trip = warehouse(BlockPolicy::Error).instructions(&robot, "AAARAA").unwrap();
+    assert_eq!(robot.instructions("AAARAA").unwrap(), trip.robot);
+    assert!(trip.blocked.is_empty());
+}
+
+#[test]
+fn advance_into_obstacle() {
+    let world = warehouse(BlockPolicy::Stop);
+    let robot = Robot::new(1, 1, Direction::East);
+    assert_eq!(
+        Err(Blocked { index: 0, cell: (2, 1), reason: BlockReason::Obstacle }),
+        world.advance(&robot)
+    );
+}
+
+#[test]
+fn stop_policy_skips_and_reports_blocked_moves() {
+    let world = warehouse(BlockPolicy::Stop);
+    let trip = world.instructions(&Robot::new(1, 1, Direction::East), "AALAAAAA").unwrap();
+    assert_eq!((1, 4), trip.robot.position());
+    assert_eq!(
+        vec![
+            Blocked { index: 0, cell: (2, 1), reason: BlockReason::Obstacle },
+            Blocked { index: 1, cell: (2, 1), reason: BlockReason::Obstacle },
+            Blocked { index: 6, cell: (1, 5), reason: BlockReason::OutOfBounds },
+            Blocked { index: 7, cell: (1, 5), reason: BlockReason::OutOfBounds },
+        ],
+        trip.blocked
+    );
+}
+
+#[test]
+fn error_policy_fails_on_first_blocked_move() {
+    let world = warehouse(BlockPolicy::Error);
+    assert_eq!(
+        Err(WorldError::Blocked(Blocked { index: 1, cell: (-1, 0), reason: BlockReason::OutOfBounds })),
+        world.instructions(&Robot::new(0, 0, Direction::South), "RAA")
+    );
+}
+
+#[test]
+fn error_message() {
+    let world = warehouse(BlockPolicy::Error);
+    let err = world.instructions(&Robot::new(1, 2, Direction::East), "A").unwrap_err();
+    assert_eq!("instruction 0 blocked at (2, 2): obstacle", err.to_string());
+}
+
+#[test]
+fn invalid_instructions_are_rejected_before_moving() {
+    let world = warehouse(BlockPolicy::Stop);
+    assert_eq!(
+        Err(WorldError::Instruction(InstructionError { index: 2, found: 'x' })),
+        world.instructions(&Robot::new(0, 0, Direction::North), "AAx")
+    );
+}
+
+#[test]
+fn wrap_policy_is_a_torus() {
+    let world = warehouse(BlockPolicy::Wrap);
+    let trip = world.instructions(&Robot::new(0, 0, Direction::West), "A").unwrap();
+    assert_eq!((4, 0), trip.robot.position());
+    let trip = world.instructions(&Robot::new(3, 4, Direction::North), "AA").unwrap();
+    assert_eq!((3, 1), trip.robot.position());
+    assert!(trip.blocked.is_empty());
+}
+
+#[test]
+fn wrap_policy_still_stops_at_obstacles() {
+    let world = World::new(3, 1).unwrap().obstacle((0, 0)).policy(BlockPolicy::Wrap);
+    let trip = world.instructions(&Robot::new(2, 0, Direction::East), "A").unwrap();
+    assert_eq!((2, 0), trip.robot.position());
+    assert_eq!(vec![Blocked { index: 0, cell: (0, 0), reason: BlockReason::Obstacle }], trip.blocked);
+}
@@ -0,0 +1,334 @@
+// Il cuore di slugify, usabile anche da altri crate: slugify con le opzioni di default,
+// slugify_with con separatore, lunghezza massima, maiuscole, stopword e lingua scelti,
+// e la rinomina dei file di una cartella.
+
+mod locale;
+pub mod rename;
+mod translit;
+pub use locale::Locale;
+
+pub const STOPWORDS: &[&str] = &["the", "a", "di", "il"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlugOptions {
+    separator: char,
+    max_len: Option<usize>,
+    preserve_case: bool,
+    stopwords: Vec<String>,
+    locale: Locale,
+}
+
+// il profilo di default è quello di slugify
+impl Default for SlugOptions {
+    fn default() -> Self {
+        SlugOptions { separator: '-', max_len: None, preserve_case: false, stopwords: vec![], locale: Locale::Default }
+    }
+}
+
+impl SlugOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn separator(mut self, separator: char) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    pub fn preserve_case(mut self, preserve_case: bool) -> Self {
+        self.preserve_case = preserve_case;
+        self
+    }
+
+    // parole da eliminare, confrontate con le parole già convertite ("però" elimina anche "Pero")
+    pub fn stopwords(mut self, words: &[&str]) -> Self {
+        self.stopwords = words.iter().map(|w| slugify(w)).collect();
+        self
+    }
+
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    // elimina la parola che inizia in start se è una stopword
+    fn drop_stopword(&self, result: &mut String, start: usize) -> bool {
+        let word = &result[start..];
+        if !word.is_empty() && self.stopwords.iter().any(|w| w.eq_ignore_ascii_case(word)) {
+            result.truncate(start);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+pub fn slugify(s: &str) -> String {
+    slugify_with(s, &SlugOptions::default())
+}
+
+pub fn slugify_with(s: &str, opts: &SlugOptions) -> String {
+    let sep = opts.separator;
+    let mut result = String::new();
+    let mut start = 0; // inizio della parola corrente in result
+    let mut dropped = false; // l'ultima parola era una stopword, il separatore c'è già
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() {
+            result.push(if opts.preserve_case { c } else { c.to_ascii_lowercase() });
+            dropped = false;
+            continue;
+        }
+        let mut upper = opts.preserve_case && c.is_uppercase();
+        for l in c.to_lowercase() {
+            // "İ" diventa "i" più un accento combinante
+            if l.is_ascii_alphanumeric() {
+                result.push(if upper { l.to_ascii_uppercase() } else { l });
+                upper = false;
+                dropped = false;
+                continue;
+            }
+            let cc = conv(l, opts.locale);
+            if cc == "-" {
+                dropped = opts.drop_stopword(&mut result, start) || dropped;
+                if !dropped && !result.ends_with(sep) {
+                    result.push(sep);
+                }
+                start = result.len();
+            } else if !cc.is_empty() {
+                // "Ж" -> "Zh"
+                if upper {
+                    result.push_str(&cc[..1].to_uppercase());
+                    result.push_str(&cc[1..]);
+                    upper = false;
+                } else {
+                    result.push_str(cc);
+                }
+                dropped = false;
+            }
+        }
+    }
+    opts.drop_stopword(&mut result, start);
+    if result.chars().count()>1 && result.ends_with(sep){
+        result.pop();
+    }
+    if let Some(max) = opts.max_len {
+        truncate(&mut result, max, sep);
+    }
+    result
+}
+
+// taglia a max caratteri senza spezzare le parole, a meno che la prima sia già troppo lunga
+fn truncate(s: &mut String, max: usize, sep: char) {
+    let end = match s.char_indices().nth(max) {
+        Some((end, _)) => end,
+        None => return,
+    };
+    let cut = if s[end..].starts_with(sep) {
+        end
+    } else {
+        s[..end].rfind(sep).filter(|&i| i > 0).unwrap_or(end)
+    };
+    s.truncate(cut);
+    while s.ends_with(sep) {
+        s.pop();
+    }
+}
+
+// può restituire più caratteri ("ß" -> "ss") o nessuno (accenti combinanti)
+fn conv(c: char, locale: Locale) -> &'static str {
+    locale.translit(c).unwrap_or("-") // Se il carattere non è nella tabella, restituisce "-"
+}
+
+// Sezione per gli unit test
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversione_lettera_accentata() {
+        let carattere_accentato = "é";
+        let carattere_convertito = slugify(carattere_accentato);
+        assert_eq!(carattere_convertito, "e");
+    }
+
+    #[test]
+    fn test_conversione_lettera_non_accentata() {
+        let carattere_non_accentato = "a";
+        let carattere_convertito = slugify(carattere_non_accentato);
+        assert_eq!(carattere_convertito, "a");
+    }
+
+    #[test]
+    fn test_conversione_carattere_non_amesso() {
+        let carattere_non_amesso = "#";
+        let carattere_convertito = slugify(carattere_non_amesso);
+        assert_eq!(carattere_convertito, "-");
+    }
+
+    #[test]
+    fn test_conversione_carattere_non_compreso() {
+        let carattere_non_compreso = "漢";
+        let carattere_convertito = slugify(carattere_non_compreso);
+        assert_eq!(carattere_convertito, "-");
+    }
+
+    #[test]
+    fn test_conversione_lettera_greca_accentata() {
+        assert_eq!(slugify("ῶ"), "o");
+    }
+
+    #[test]
+    fn test_conversione_piu_caratteri() {
+        assert_eq!(slugify("ß"), "ss");
+        assert_eq!(slugify("æ"), "ae");
+        assert_eq!(slugify("Ж"), "zh");
+    }
+
+    #[test]
+    fn test_slugify_cirillico() {
+        assert_eq!(slugify("Москва"), "moskva");
+        assert_eq!(slugify("Съешь щи"), "sesh-shchi");
+    }
+
+    #[test]
+    fn test_slugify_greco() {
+        assert_eq!(slugify("Αθήνα"), "athina");
+        assert_eq!(slugify("Ψυχή"), "psychi");
+    }
+
+    #[test]
+    fn test_slugify_latino_esteso() {
+        assert_eq!(slugify("Straße Œuvre"), "strasse-oeuvre");
+        assert_eq!(slugify("Łódź ǅ"), "lodz-dz");
+    }
+
+    #[test]
+    fn test_slugify_i_turca_maiuscola() {
+        // "İ" diventa "i" seguita da un punto combinante, che viene scartato
+        assert_eq!(slugify("İstanbul"), "istanbul");
+    }
+
+    #[test]
+    fn test_slugify_stringa_con_spazi() {
+        let stringa = "Ciao mondo";
+        let stringa_slugify = slugify(stringa);
+        assert_eq!(stringa_slugify, "ciao-mondo");
+    }
+
+    #[test]
+    fn test_slugify_stringa_caratteri_accentati() {
+        let stringa = "àéè";
+        let stringa_slugify = slugify(stringa);
+        assert_eq!(stringa_slugify, "aee");
+    }
+
+    #[test]
+    fn test_slugify_stringa_con_caratteri_non_validi() {
+        let stringa = "C#a#o";
+        let stringa_slugify = slugify(stringa);
+        assert_eq!(stringa_slugify, "c-a-o");
+    }
+
+    #[test]
+    fn test_slugify_stringa_con_solo_caratteri_non_validi() {
+        let stringa = "###";
+        let stringa_slugify = slugify(stringa);
+        assert_eq!(stringa_slugify, "-");
+    }
+
+    #[test]
+    fn test_slugify_stringa_con_spazio_alla_fine() {
+        let stringa = "Ciao ";
+        let stringa_slugify = slugify(stringa);
+        assert_eq!(stringa_slugify, "ciao");
+    }
+
+    #[test]
+    fn test_slugify_stringa_con_caratteri_non_validi_consecutivi_alla_fine() {
+        let stringa = "Ciao##";
+        let stringa_slugify = slugify(stringa);
+        assert_eq!(stringa_slugify, "ciao");
+    }
+
+    #[test]
+    fn test_opzioni_di_default_uguali_a_slugify() {
+        for s in ["Ciao mondo", "C#a#o", "###", "Ciao##", "#ciao", "Straße"] {
+            assert_eq!(slugify_with(s, &SlugOptions::new()), slugify(s));
+        }
+    }
+
+    #[test]
+    fn test_separatore_underscore() {
+        let opts = SlugOptions::new().separator('_');
+        assert_eq!(slugify_with("Ciao  bel mondo!", &opts), "ciao_bel_mondo");
+        assert_eq!(slugify_with("snake-case", &opts), "snake_case");
+    }
+
+    #[test]
+    fn test_lunghezza_massima_su_confine_di_parola() {
+        let opts = SlugOptions::new().max_len(12);
+        assert_eq!(slugify_with("Ciao bel mondo", &opts), "ciao-bel");
+        assert_eq!(slugify_with("Ciao bel", &opts), "ciao-bel");
+        assert_eq!(slugify_with("Ciao bel mondo", &SlugOptions::new().max_len(8)), "ciao-bel");
+    }
+
+    #[test]
+    fn test_lunghezza_massima_parola_troppo_lunga() {
+        let opts = SlugOptions::new().max_len(5);
+        assert_eq!(slugify_with("Precipitevolissimevolmente", &opts), "preci");
+        assert_eq!(slugify_with("Ciao", &SlugOptions::new().max_len(0)), "");
+    }
+
+    #[test]
+    fn test_mantieni_maiuscole() {
+        let opts = SlugOptions::new().preserve_case(true);
+        assert_eq!(slugify_with("Ciao Mondo", &opts), "Ciao-Mondo");
+        assert_eq!(slugify_with("Élan Жук İzmir", &opts), "Elan-Zhuk-Izmir");
+    }
+
+    #[test]
+    fn test_rimozione_stopwords() {
+        let opts = SlugOptions::new().stopwords(STOPWORDS);
+        assert_eq!(slugify_with("Il nome della rosa", &opts), "nome-della-rosa");
+        assert_eq!(slugify_with("The Lord of the Rings", &opts), "lord-of-rings");
+        assert_eq!(slugify_with("Storia di un impiegato", &opts), "storia-un-impiegato");
+        assert_eq!(slugify_with("Una casa a Roma, il", &opts), "una-casa-roma");
+        // le stopword sono parole intere
+        assert_eq!(slugify_with("Theater Ailo", &opts), "theater-ailo");
+        let opts = SlugOptions::new().stopwords(&["però"]);
+        assert_eq!(slugify_with("Pero, però no", &opts), "no");
+    }
+
+    #[test]
+    fn test_opzioni_combinate() {
+        let opts = SlugOptions::new().separator('_').max_len(15).preserve_case(true).stopwords(STOPWORDS);
+        assert_eq!(slugify_with("The Quick Brown Fox", &opts), "Quick_Brown_Fox");
+        assert_eq!(slugify_with("The Quick Brown Fox Jumps", &opts), "Quick_Brown_Fox");
+    }
+
+    #[test]
+    fn test_locale_tedesco() {
+        let opts = SlugOptions::new().locale(Locale::De);
+        assert_eq!(slugify_with("Müller Straße", &opts), "mueller-strasse");
+        assert_eq!(slugify_with("Schön", &opts.clone().preserve_case(true)), "Schoen");
+        assert_eq!(slugify_with("Ärger", &opts.preserve_case(true)), "Aerger");
+    }
+
+    #[test]
+    fn test_locale_svedese() {
+        let opts = SlugOptions::new().locale(Locale::Sv);
+        assert_eq!(slugify_with("Ångström", &opts), "aangstroem");
+        // i caratteri non ridefiniti usano la tabella di default
+        assert_eq!(slugify_with("Malmö är ß", &opts), "malmoe-aer-ss");
+    }
+
+    #[test]
+    fn test_locale_default() {
+        assert_eq!(slugify_with("Müller Ångström", &SlugOptions::new().locale(Locale::Default)), "muller-angstrom");
+    }
+}
@@ -0,0 +1,32 @@
+// Regole di traslitterazione per lingua, controllate prima della tabella di default:
+// sono elencati solo i caratteri che una lingua scrive in modo diverso.
+
+use crate::translit::translit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Locale {
+    #[default]
+    Default,
+    // tedesco
+    De,
+    // svedese
+    Sv,
+}
+
+const DE: &[(char, &str)] = &[('ß', "ss"), ('ä', "ae"), ('ö', "oe"), ('ü', "ue")];
+const SV: &[(char, &str)] = &[('ä', "ae"), ('å', "aa"), ('ö', "oe")];
+
+impl Locale {
+    fn overrides(self) -> &'static [(char, &'static str)] {
+        match self {
+            Locale::Default => &[],
+            Locale::De => DE,
+            Locale::Sv => SV,
+        }
+    }
+
+    // None se il carattere non è in nessuna delle due tabelle
+    pub fn translit(self, c: char) -> Option<&'static str> {
+        self.overrides().iter().find(|&&(k, _)| k == c).map(|&(_, s)| s).or_else(|| translit(c))
+    }
+}
@@ -1,40 +1,54 @@
 //use std::env;
-use clap::Parser;
-const SUBS_I: &str = "àáâäæãåāăąçćčđďèéêëēėęěğǵḧîïíīįìıİłḿñńǹňôöòóœøōõőṕŕřßśšşșťțûüùúūǘůűųẃẍÿýžźż";
-const SUBS_O: &str = "aaaaaaaaaacccddeeeeeeeegghiiiiiiiilmnnnnoooooooooprrsssssttuuuuuuuuuwxyyzzz";
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use clap::{Parser, Subcommand};
+
+use slugify::{rename, slugify_with, Locale, SlugOptions, STOPWORDS};
 
 #[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
-    slug_in: String, // input string
-}
-
-fn slugify(s: &str) -> String {
-    let mut result = String::new();
-    for c in s.to_lowercase().chars(){
-        if c.is_ascii_alphanumeric(){
-            result.push(c);
-        } else {
-            let cc = conv(c);
-            if cc == '-' {
-                if !result.ends_with('-') {
-                    result.push(cc);
-                }
-            } else {
-                result.push(cc);
-            }
-        }
-    }
-    if result.len()>1 && result.ends_with('-'){
-        result.pop();
-    }
-    result
+    slug_in: Option<String>, // input string
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// separator between words
+    #[arg(long, global = true, default_value_t = '-')]
+    separator: char,
+    /// maximum length, the slug is cut at a word boundary
+    #[arg(long, global = true)]
+    max_len: Option<usize>,
+    /// keep upper case letters
+    #[arg(long, global = true)]
+    preserve_case: bool,
+    /// remove the common stopwords ("the", "a", "di", "il")
+    #[arg(long, global = true)]
+    stopwords: bool,
+    /// language specific transliteration, e.g. "ä" -> "ae" in German
+    #[arg(long, global = true, value_enum)]
+    locale: Option<Locale>,
 }
 
-fn conv(c: char) -> char {
-   match SUBS_I.chars().position(|x| x == c) {
-        Some(index) => SUBS_O.chars().nth(index).unwrap_or('-'), // Se il carattere non è in SUBS_O, restituisce "-" altrimenti il carattere corretto
-        None => '-' // Se il carattere non è in SUBS_I, restituisce "-"
-    }
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// slugify every line of the files, or of stdin if there are none, one slug per line
+    Batch {
+        files: Vec<PathBuf>,
+    },
+    /// rename the files in a directory and its subdirectories to their slug, keeping the extension
+    Rename {
+        dir: PathBuf,
+        /// only print what would be renamed
+        #[arg(long)]
+        dry_run: bool,
+        /// where to write the rename log [default: <DIR>/.slugify-rename.log]
+        #[arg(long)]
+        log: Option<PathBuf>,
+    },
+    /// undo the renames listed in a log
+    Undo {
+        log: PathBuf,
+    },
 }
 
 fn main() {
@@ -56,89 +70,102 @@ fn main() {
      /* UTILIZZO ARGS CON CLAP */
 
     let args = Args::parse();
-
-
-    // Chiama la funzione slugify con la stringa fornita
-    let slugified_string = slugify(&args.slug_in);
-
-    // Stampa l'output con il prefisso "slug:"
-    println!("slug: {}", slugified_string);
+    if let Err(e) = run(args) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
 }
 
-// Sezione per gli unit test
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_conversione_lettera_accentata() {
-        let carattere_accentato = "é";
-        let carattere_convertito = slugify(carattere_accentato);
-        assert_eq!(carattere_convertito, "e");
+// una riga di output per ogni riga di input
+fn slugify_lines<R: BufRead, W: Write>(input: R, out: &mut W, opts: &SlugOptions) -> io::Result<()> {
+    for line in input.lines() {
+        writeln!(out, "{}", slugify_with(&line?, opts))?;
     }
+    Ok(())
+}
 
-    #[test]
-    fn test_conversione_lettera_non_accentata() {
-        let carattere_non_accentato = "a";
-        let carattere_convertito = slugify(carattere_non_accentato);
-        assert_eq!(carattere_convertito, "a");
+fn run(args: Args) -> io::Result<()> {
+    let mut opts = SlugOptions::new().separator(args.separator).preserve_case(args.preserve_case);
+    if let Some(max) = args.max_len {
+        opts = opts.max_len(max);
     }
-
-    #[test]
-    fn test_conversione_carattere_non_amesso() {
-        let carattere_non_amesso = "#";
-        let carattere_convertito = slugify(carattere_non_amesso);
-        assert_eq!(carattere_convertito, "-");
+    if args.stopwords {
+        opts = opts.stopwords(STOPWORDS);
     }
-
-    #[test]
-    fn test_conversione_carattere_accentato_non_compreso() {
-        let carattere_accentato_non_compreso = "ῶ";
-        let carattere_convertito = slugify(carattere_accentato_non_compreso);
-        assert_eq!(carattere_convertito, "-");
-    }
-
-    #[test]
-    fn test_slugify_stringa_con_spazi() {
-        let stringa = "Ciao mondo";
-        let stringa_slugify = slugify(stringa);
-        assert_eq!(stringa_slugify, "ciao-mondo");
+    if let Some(locale) = args.locale {
+        opts = opts.locale(locale);
     }
 
-    #[test]
-    fn test_slugify_stringa_caratteri_accentati() {
-        let stringa = "àéè";
-        let stringa_slugify = slugify(stringa);
-        assert_eq!(stringa_slugify, "aee");
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    match args.command {
+        None => {
+            let Some(slug_in) = args.slug_in else {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "nothing to slugify, see --help"));
+            };
+            // Chiama la funzione slugify con la stringa fornita
+            let slugified_string = slugify_with(&slug_in, &opts);
+
+            // Stampa l'output con il prefisso "slug:"
+            writeln!(out, "slug: {}", slugified_string)?;
+        }
+        Some(Command::Batch { files }) if files.is_empty() => slugify_lines(io::stdin().lock(), &mut out, &opts)?,
+        Some(Command::Batch { files }) => {
+            for f in files {
+                let file = File::open(&f).map_err(|e| io::Error::new(e.kind(), format!("{}: {}", f.display(), e)))?;
+                slugify_lines(BufReader::new(file), &mut out, &opts)?;
+            }
+        }
+        Some(Command::Rename { dir, dry_run, log }) => {
+            let plan = rename::plan(&dir, &opts)?;
+            for r in &plan {
+                writeln!(out, "{} -> {}", r.from.display(), r.to.display())?;
+            }
+            if plan.is_empty() {
+                writeln!(out, "nothing to rename")?;
+            } else if !dry_run {
+                let log = log.unwrap_or_else(|| dir.join(".slugify-rename.log"));
+                rename::apply(&plan, &log)?;
+                writeln!(out, "{} files renamed, undo with: slugify undo {}", plan.len(), log.display())?;
+            }
+        }
+        Some(Command::Undo { log }) => {
+            let done = rename::undo(Path::new(&log))?;
+            for r in &done {
+                writeln!(out, "{} -> {}", r.from.display(), r.to.display())?;
+            }
+        }
     }
+    Ok(())
+}
 
-    #[test]
-    fn test_slugify_stringa_con_caratteri_non_validi() {
-        let stringa = "C#a#o";
-        let stringa_slugify = slugify(stringa);
-        assert_eq!(stringa_slugify, "c-a-o");
-    }
+// Sezione per gli unit test
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn test_slugify_stringa_con_solo_caratteri_non_validi() {
-        let stringa = "###";
-        let stringa_slugify = slugify(stringa);
-        assert_eq!(stringa_slugify, "-");
+    fn test_batch_una_riga_per_slug() {
+        let input = "Ciao mondo\nàéè\n\nC#a#o\n";
+        let mut out = vec![];
+        slugify_lines(input.as_bytes(), &mut out, &SlugOptions::new()).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "ciao-mondo\naee\n\nc-a-o\n");
     }
 
     #[test]
-    fn test_slugify_stringa_con_spazio_alla_fine() {
-        let stringa = "Ciao ";
-        let stringa_slugify = slugify(stringa);
-        assert_eq!(stringa_slugify, "ciao");
+    fn test_batch_con_opzioni() {
+        let mut out = vec![];
+        slugify_lines("The Cat\r\nIl Gatto".as_bytes(), &mut out, &SlugOptions::new().separator('_').stopwords(STOPWORDS)).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "cat\ngatto\n");
     }
 
     #[test]
-    fn test_slugify_stringa_con_caratteri_non_validi_consecutivi_alla_fine() {
-        let stringa = "Ciao##";
-        let stringa_slugify = slugify(stringa);
-        assert_eq!(stringa_slugify, "ciao");
+    fn test_argomenti() {
+        let args = Args::try_parse_from(["slugify", "Ciao mondo", "--separator", "_"]).unwrap();
+        assert_eq!(args.slug_in.as_deref(), Some("Ciao mondo"));
+        assert!(args.command.is_none());
+        let args = Args::try_parse_from(["slugify", "rename", "dir", "--dry-run", "--locale", "de"]).unwrap();
+        assert!(matches!(args.command, Some(Command::Rename { dry_run: true, .. })));
+        assert_eq!(args.locale, Some(Locale::De));
     }
-
-
 }
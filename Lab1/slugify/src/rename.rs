@@ -0,0 +1,283 @@
+// Rinomina i file di una cartella e delle sottocartelle con il loro slug, mantenendo l'estensione:
+// "Foto Mare.JPG" -> "foto-mare.JPG". Le cartelle e i file nascosti restano come sono.
+// Ogni rinomina fatta è scritta nel log, una per riga come "vecchio\tnuovo",
+// così undo può riportare tutto com'era.
+
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{slugify_with, SlugOptions};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rename {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+// nuovo nome del file; n > 1 aggiunge un suffisso per evitare le collisioni.
+// Se lo slug del nome è vuoto o solo un separatore ("The" con le stopword, "漢") resta il nome
+// originale: ".txt" sarebbe un file nascosto e "-.txt" sembrerebbe un'opzione
+fn slug_name(name: &str, n: usize, opts: &SlugOptions) -> String {
+    let path = Path::new(name);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or(name);
+    let mut new = slugify_with(stem, opts);
+    if new.chars().all(|c| c == opts.separator) {
+        new = stem.to_string();
+    }
+    if n > 1 {
+        new.push(opts.separator);
+        new.push_str(&n.to_string());
+    }
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        new.push('.');
+        new.push_str(ext);
+    }
+    new
+}
+
+fn plan_dir(dir: &Path, opts: &SlugOptions, plan: &mut Vec<Rename>) -> io::Result<()> {
+    let mut entries = vec![];
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        entries.push((entry.path(), entry.file_type()?.is_dir()));
+    }
+    entries.sort();
+    // i nomi già presenti e quelli assegnati: un file non prende mai il nome di un altro
+    let mut taken: HashSet<OsString> = entries.iter().filter_map(|(p, _)| p.file_name()).map(|n| n.to_owned()).collect();
+
+    for (path, is_dir) in entries {
+        // i nomi che non sono UTF-8 o che non starebbero su una riga del log non si toccano
+        let name = match path.file_name().and_then(|n| n.to_str()) {
+            Some(n) if !n.starts_with('.') && !n.contains(['\t', '\n']) => n,
+            _ => continue,
+        };
+        if is_dir {
+            plan_dir(&path, opts, plan)?;
+            continue;
+        }
+        let mut n = 1;
+        let mut new = slug_name(name, n, opts);
+        if new == name {
+            continue;
+        }
+        while taken.contains(OsString::from(&new).as_os_str()) {
+            n += 1;
+            new = slug_name(name, n, opts);
+        }
+        taken.insert(OsString::from(&new));
+        plan.push(Rename { from: path.clone(), to: path.with_file_name(new) });
+    }
+    Ok(())
+}
+
+// le rinomine da fare, senza toccare nulla (anche per il dry run)
+pub fn plan(dir: &Path, opts: &SlugOptions) -> io::Result<Vec<Rename>> {
+    let mut plan = vec![];
+    plan_dir(&fs::canonicalize(dir)?, opts, &mut plan)?;
+    Ok(plan)
+}
+
+// il log viene scritto dopo ogni rinomina, così anche un errore a metà si può annullare;
+// un log già esistente viene allungato, così undo annulla anche le rinomine precedenti
+pub fn apply(plan: &[Rename], log: &Path) -> io::Result<()> {
+    // senza rinomine il log non si crea e non si tocca
+    if plan.is_empty() {
+        return Ok(());
+    }
+    let mut out = BufWriter::new(OpenOptions::new().create(true).append(true).open(log)?);
+    for r in plan {
+        // fs::rename sovrascriverebbe un file comparso nel frattempo
+        if r.to.exists() {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", r.to.display())));
+        }
+        fs::rename(&r.from, &r.to)?;
+        writeln!(out, "{}\t{}", r.from.display(), r.to.display())?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+// annulla le rinomine del log, dall'ultima alla prima, e poi cancella il log. Dopo ogni
+// rinomina annullata il log è riscritto senza di essa, così dopo un errore si può riprendere
+pub fn undo(log: &Path) -> io::Result<Vec<Rename>> {
+    let text = fs::read_to_string(log)?;
+    let mut lines: Vec<&str> = text.lines().filter(|l| !l.is_empty()).collect();
+    let mut done = vec![];
+    while let Some(&line) = lines.last() {
+        let (from, to) = line
+            .split_once('\t')
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("invalid log line '{}'", line)))?;
+        let r = Rename { from: PathBuf::from(to), to: PathBuf::from(from) };
+        if r.to.exists() {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", r.to.display())));
+        }
+        fs::rename(&r.from, &r.to)?;
+        done.push(r);
+        lines.pop();
+        let rest: String = lines.iter().map(|l| format!("{}\n", l)).collect();
+        fs::write(log, rest)?;
+    }
+    fs::remove_file(log)?;
+    Ok(done)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // cartella temporanea con i file indicati, cancellata alla fine del test
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str, files: &[&str]) -> TempDir {
+            let dir = std::env::temp_dir().join(format!("slugify_{}_{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            for f in files {
+                let path = dir.join(f);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, f).unwrap();
+            }
+            TempDir(fs::canonicalize(&dir).unwrap())
+        }
+
+        fn names(&self) -> Vec<String> {
+            let mut names = vec![];
+            let mut dirs = vec![self.0.clone()];
+            while let Some(d) = dirs.pop() {
+                for e in fs::read_dir(d).unwrap() {
+                    let p = e.unwrap().path();
+                    if p.is_dir() {
+                        dirs.push(p);
+                    } else {
+                        names.push(p.strip_prefix(&self.0).unwrap().to_str().unwrap().to_string());
+                    }
+                }
+            }
+            names.sort();
+            names
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_nome_con_estensione() {
+        let opts = SlugOptions::new();
+        assert_eq!(slug_name("Foto Mare.JPG", 1, &opts), "foto-mare.JPG");
+        assert_eq!(slug_name("Foto Mare.JPG", 2, &opts), "foto-mare-2.JPG");
+        assert_eq!(slug_name("Leggimi", 1, &opts), "leggimi");
+        assert_eq!(slug_name("Città.tar.gz", 1, &opts.separator('_')), "citta_tar.gz");
+    }
+
+    #[test]
+    fn test_dry_run_non_tocca_nulla() {
+        let dir = TempDir::new("dry_run", &["Foto Mare.jpg", "già-ok.txt", "ok.txt"]);
+        let plan = plan(&dir.0, &SlugOptions::new()).unwrap();
+        assert_eq!(
+            plan,
+            vec![
+                Rename { from: dir.0.join("Foto Mare.jpg"), to: dir.0.join("foto-mare.jpg") },
+                Rename { from: dir.0.join("già-ok.txt"), to: dir.0.join("gia-ok.txt") },
+            ]
+        );
+        assert_eq!(dir.names(), vec!["Foto Mare.jpg", "già-ok.txt", "ok.txt"]);
+    }
+
+    #[test]
+    fn test_rinomina_ricorsiva() {
+        let dir = TempDir::new("ricorsiva", &["Sotto Cartella/Ciao Mondo.md", "Résumé.PDF", ".Nascosto"]);
+        let plan = plan(&dir.0, &SlugOptions::new()).unwrap();
+        apply(&plan, &dir.0.join(".log")).unwrap();
+        assert_eq!(dir.names(), vec![".Nascosto", ".log", "Sotto Cartella/ciao-mondo.md", "resume.PDF"]);
+    }
+
+    #[test]
+    fn test_collisioni() {
+        let dir = TempDir::new("collisioni", &["Ciao Mondo.txt", "ciao mondo.txt", "ciao-mondo.txt", "CIAO_MONDO.txt"]);
+        let plan = plan(&dir.0, &SlugOptions::new()).unwrap();
+        apply(&plan, &dir.0.join(".log")).unwrap();
+        assert_eq!(dir.names(), vec![".log", "ciao-mondo-2.txt", "ciao-mondo-3.txt", "ciao-mondo-4.txt", "ciao-mondo.txt"]);
+        // il file che aveva già il nome giusto non è stato toccato
+        assert_eq!(fs::read_to_string(dir.0.join("ciao-mondo.txt")).unwrap(), "ciao-mondo.txt");
+    }
+
+    #[test]
+    fn test_undo_dal_log() {
+        let dir = TempDir::new("undo", &["A B.txt", "Sub/C D.txt", "a-b.txt"]);
+        let log = dir.0.join(".log");
+        apply(&plan(&dir.0, &SlugOptions::new()).unwrap(), &log).unwrap();
+        assert_eq!(dir.names(), vec![".log", "Sub/c-d.txt", "a-b-2.txt", "a-b.txt"]);
+
+        assert_eq!(undo(&log).unwrap().len(), 2);
+        assert_eq!(dir.names(), vec!["A B.txt", "Sub/C D.txt", "a-b.txt"]);
+        assert!(undo(&log).is_err());
+    }
+
+    #[test]
+    fn test_seconda_rinomina_non_perde_il_log() {
+        let dir = TempDir::new("due_volte", &["A B.txt"]);
+        let log = dir.0.join(".log");
+        apply(&plan(&dir.0, &SlugOptions::new()).unwrap(), &log).unwrap();
+        // niente da rinominare: il log resta com'è
+        let prima = fs::read_to_string(&log).unwrap();
+        apply(&plan(&dir.0, &SlugOptions::new()).unwrap(), &log).unwrap();
+        assert_eq!(fs::read_to_string(&log).unwrap(), prima);
+
+        fs::write(dir.0.join("C D.txt"), "").unwrap();
+        apply(&plan(&dir.0, &SlugOptions::new()).unwrap(), &log).unwrap();
+        assert_eq!(dir.names(), vec![".log", "a-b.txt", "c-d.txt"]);
+        assert_eq!(undo(&log).unwrap().len(), 2);
+        assert_eq!(dir.names(), vec!["A B.txt", "C D.txt"]);
+    }
+
+    #[test]
+    fn test_piano_vuoto_non_crea_il_log() {
+        let dir = TempDir::new("piano_vuoto", &["ok.txt"]);
+        apply(&plan(&dir.0, &SlugOptions::new()).unwrap(), &dir.0.join(".log")).unwrap();
+        assert_eq!(dir.names(), vec!["ok.txt"]);
+    }
+
+    #[test]
+    fn test_nomi_senza_slug_restano_uguali() {
+        let dir = TempDir::new("senza_slug", &["The.txt", "漢.txt", "Il Mare.txt"]);
+        let opts = SlugOptions::new().stopwords(&["the", "il"]);
+        assert_eq!(slug_name("The.txt", 1, &opts), "The.txt");
+        assert_eq!(slug_name("漢.txt", 1, &opts), "漢.txt");
+        assert_eq!(slug_name("漢.txt", 2, &opts), "漢-2.txt");
+        assert_eq!(slug_name("Ciao.txt", 1, &SlugOptions::new().max_len(0)), "Ciao.txt");
+        let plan = plan(&dir.0, &opts).unwrap();
+        assert_eq!(plan, vec![Rename { from: dir.0.join("Il Mare.txt"), to: dir.0.join("mare.txt") }]);
+    }
+
+    #[test]
+    fn test_undo_riprende_dopo_un_errore() {
+        let dir = TempDir::new("undo_ripresa", &["A B.txt", "C D.txt"]);
+        let log = dir.0.join(".log");
+        apply(&plan(&dir.0, &SlugOptions::new()).unwrap(), &log).unwrap();
+        // c-d.txt viene annullato, a-b.txt no
+        fs::write(dir.0.join("A B.txt"), "nuovo").unwrap();
+        assert_eq!(undo(&log).unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(dir.names(), vec![".log", "A B.txt", "C D.txt", "a-b.txt"]);
+
+        fs::remove_file(dir.0.join("A B.txt")).unwrap();
+        assert_eq!(undo(&log).unwrap().len(), 1);
+        assert_eq!(dir.names(), vec!["A B.txt", "C D.txt"]);
+    }
+
+    #[test]
+    fn test_undo_non_sovrascrive() {
+        let dir = TempDir::new("undo_conflitto", &["A B.txt"]);
+        let log = dir.0.join(".log");
+        apply(&plan(&dir.0, &SlugOptions::new()).unwrap(), &log).unwrap();
+        fs::write(dir.0.join("A B.txt"), "nuovo").unwrap();
+        assert_eq!(undo(&log).unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(dir.0.join("A B.txt")).unwrap(), "nuovo");
+    }
+}
@@ -0,0 +1,123 @@
+// Tabella di traslitterazione: lettere minuscole non ASCII di Latin-1, Latin Extended, greco e cirillico
+// con la loro grafia ASCII, che può essere più lunga di un carattere ("ß" -> "ss", "ж" -> "zh").
+// I segni combinanti diventano "", così gli accenti decomposti vengono eliminati.
+// La tabella è ordinata per carattere, per la ricerca binaria.
+const TABLE: &[(char, &str)] = &[
+    ('ß', "ss"), ('à', "a"), ('á', "a"), ('â', "a"), ('ã', "a"), ('ä', "a"), ('å', "a"),
+    ('æ', "ae"), ('ç', "c"), ('è', "e"), ('é', "e"), ('ê', "e"), ('ë', "e"), ('ì', "i"), ('í', "i"),
+    ('î', "i"), ('ï', "i"), ('ð', "d"), ('ñ', "n"), ('ò', "o"), ('ó', "o"), ('ô', "o"), ('õ', "o"),
+    ('ö', "o"), ('ø', "o"), ('ù', "u"), ('ú', "u"), ('û', "u"), ('ü', "u"), ('ý', "y"), ('þ', "th"),
+    ('ÿ', "y"), ('ā', "a"), ('ă', "a"), ('ą', "a"), ('ć', "c"), ('ĉ', "c"), ('ċ', "c"), ('č', "c"),
+    ('ď', "d"), ('đ', "d"), ('ē', "e"), ('ĕ', "e"), ('ė', "e"), ('ę', "e"), ('ě', "e"), ('ĝ', "g"),
+    ('ğ', "g"), ('ġ', "g"), ('ģ', "g"), ('ĥ', "h"), ('ħ', "h"), ('ĩ', "i"), ('ī', "i"), ('ĭ', "i"),
+    ('į', "i"), ('ı', "i"), ('ĳ', "ij"), ('ĵ', "j"), ('ķ', "k"), ('ĸ', "k"), ('ĺ', "l"), ('ļ', "l"),
+    ('ľ', "l"), ('ŀ', "l"), ('ł', "l"), ('ń', "n"), ('ņ', "n"), ('ň', "n"), ('ŉ', "n"), ('ŋ', "ng"),
+    ('ō', "o"), ('ŏ', "o"), ('ő', "o"), ('œ', "oe"), ('ŕ', "r"), ('ŗ', "r"), ('ř', "r"), ('ś', "s"),
+    ('ŝ', "s"), ('ş', "s"), ('š', "s"), ('ţ', "t"), ('ť', "t"), ('ŧ', "t"), ('ũ', "u"), ('ū', "u"),
+    ('ŭ', "u"), ('ů', "u"), ('ű', "u"), ('ų', "u"), ('ŵ', "w"), ('ŷ', "y"), ('ź', "z"), ('ż', "z"),
+    ('ž', "z"), ('ſ', "s"), ('ƀ', "b"), ('ƅ', ""), ('ƈ', "c"), ('ƌ', "d"), ('ƍ', ""), ('ƒ', "f"),
+    ('ƕ', "hv"), ('ƙ', "k"), ('ƚ', "l"), ('ƞ', "n"), ('ơ', "o"), ('ƣ', "oi"), ('ƥ', "p"),
+    ('ƨ', "s"), ('ƫ', "t"), ('ƭ', "t"), ('ư', "u"), ('ƴ', "y"), ('ƶ', "z"), ('ƹ', "zh"), ('ƽ', ""),
+    ('ƾ', "ts"), ('ƿ', "w"), ('ǀ', ""), ('ǁ', ""), ('ǂ', ""), ('ǃ', ""), ('ǆ', "dz"), ('ǉ', "lj"),
+    ('ǌ', "nj"), ('ǎ', "a"), ('ǐ', "i"), ('ǒ', "o"), ('ǔ', "u"), ('ǖ', "u"), ('ǘ', "u"), ('ǚ', "u"),
+    ('ǜ', "u"), ('ǝ', "e"), ('ǟ', "a"), ('ǡ', "a"), ('ǣ', "ae"), ('ǥ', "g"), ('ǧ', "g"), ('ǩ', "k"),
+    ('ǫ', "o"), ('ǭ', "o"), ('ǯ', "zh"), ('ǰ', "j"), ('ǳ', "dz"), ('ǵ', "g"), ('ǹ', "n"),
+    ('ǻ', "a"), ('ǽ', "ae"), ('ǿ', "o"), ('ȁ', "a"), ('ȃ', "a"), ('ȅ', "e"), ('ȇ', "e"), ('ȉ', "i"),
+    ('ȋ', "i"), ('ȍ', "o"), ('ȏ', "o"), ('ȑ', "r"), ('ȓ', "r"), ('ȕ', "u"), ('ȗ', "u"), ('ș', "s"),
+    ('ț', "t"), ('ȝ', "y"), ('ȟ', "h"), ('ȡ', "d"), ('ȣ', "ou"), ('ȥ', "z"), ('ȧ', "a"), ('ȩ', "e"),
+    ('ȫ', "o"), ('ȭ', "o"), ('ȯ', "o"), ('ȱ', "o"), ('ȳ', "y"), ('ȴ', "l"), ('ȵ', "n"), ('ȶ', "t"),
+    ('ȷ', "j"), ('ȸ', "db"), ('ȹ', "qp"), ('ȼ', "c"), ('ȿ', "s"), ('ɀ', "z"), ('ɂ', ""), ('ɇ', "e"),
+    ('ɉ', "j"), ('ɋ', "q"), ('ɍ', "r"), ('ɏ', "y"), ('ɓ', "b"), ('ɗ', "d"), ('ə', "e"), ('ɛ', "e"),
+    ('ɠ', "g"), ('ɣ', "g"), ('ɨ', "i"), ('ɩ', "i"), ('ɲ', "n"), ('ɵ', "o"), ('ʀ', "r"), ('ʃ', "sh"),
+    ('ʈ', "t"), ('ʉ', "u"), ('ʋ', "v"), ('ʒ', "zh"), ('\u{0300}', ""), ('\u{0301}', ""),
+    ('\u{0302}', ""), ('\u{0303}', ""), ('\u{0304}', ""), ('\u{0305}', ""), ('\u{0306}', ""),
+    ('\u{0307}', ""), ('\u{0308}', ""), ('\u{0309}', ""), ('\u{030a}', ""), ('\u{030b}', ""),
+    ('\u{030c}', ""), ('\u{030d}', ""), ('\u{030e}', ""), ('\u{030f}', ""), ('\u{0310}', ""),
+    ('\u{0311}', ""), ('\u{0312}', ""), ('\u{0313}', ""), ('\u{0314}', ""), ('\u{0315}', ""),
+    ('\u{0316}', ""), ('\u{0317}', ""), ('\u{0318}', ""), ('\u{0319}', ""), ('\u{031a}', ""),
+    ('\u{031b}', ""), ('\u{031c}', ""), ('\u{031d}', ""), ('\u{031e}', ""), ('\u{031f}', ""),
+    ('\u{0320}', ""), ('\u{0321}', ""), ('\u{0322}', ""), ('\u{0323}', ""), ('\u{0324}', ""),
+    ('\u{0325}', ""), ('\u{0326}', ""), ('\u{0327}', ""), ('\u{0328}', ""), ('\u{0329}', ""),
+    ('\u{032a}', ""), ('\u{032b}', ""), ('\u{032c}', ""), ('\u{032d}', ""), ('\u{032e}', ""),
+    ('\u{032f}', ""), ('\u{0330}', ""), ('\u{0331}', ""), ('\u{0332}', ""), ('\u{0333}', ""),
+    ('\u{0334}', ""), ('\u{0335}', ""), ('\u{0336}', ""), ('\u{0337}', ""), ('\u{0338}', ""),
+    ('\u{0339}', ""), ('\u{033a}', ""), ('\u{033b}', ""), ('\u{033c}', ""), ('\u{033d}', ""),
+    ('\u{033e}', ""), ('\u{033f}', ""), ('\u{0340}', ""), ('\u{0341}', ""), ('\u{0342}', ""),
+    ('\u{0343}', ""), ('\u{0344}', ""), ('\u{0345}', ""), ('\u{0346}', ""), ('\u{0347}', ""),
+    ('\u{0348}', ""), ('\u{0349}', ""), ('\u{034a}', ""), ('\u{034b}', ""), ('\u{034c}', ""),
+    ('\u{034d}', ""), ('\u{034e}', ""), ('͏', ""), ('\u{0350}', ""), ('\u{0351}', ""),
+    ('\u{0352}', ""), ('\u{0353}', ""), ('\u{0354}', ""), ('\u{0355}', ""), ('\u{0356}', ""),
+    ('\u{0357}', ""), ('\u{0358}', ""), ('\u{0359}', ""), ('\u{035a}', ""), ('\u{035b}', ""),
+    ('\u{035c}', ""), ('\u{035d}', ""), ('\u{035e}', ""), ('\u{035f}', ""), ('\u{0360}', ""),
+    ('\u{0361}', ""), ('\u{0362}', ""), ('\u{0363}', ""), ('\u{0364}', ""), ('\u{0365}', ""),
+    ('\u{0366}', ""), ('\u{0367}', ""), ('\u{0368}', ""), ('\u{0369}', ""), ('\u{036a}', ""),
+    ('\u{036b}', ""), ('\u{036c}', ""), ('\u{036d}', ""), ('\u{036e}', ""), ('\u{036f}', ""),
+    ('ΐ', "i"), ('ά', "a"), ('έ', "e"), ('ή', "i"), ('ί', "i"), ('ΰ', "y"), ('α', "a"), ('β', "v"),
+    ('γ', "g"), ('δ', "d"), ('ε', "e"), ('ζ', "z"), ('η', "i"), ('θ', "th"), ('ι', "i"), ('κ', "k"),
+    ('λ', "l"), ('μ', "m"), ('ν', "n"), ('ξ', "x"), ('ο', "o"), ('π', "p"), ('ρ', "r"), ('ς', "s"),
+    ('σ', "s"), ('τ', "t"), ('υ', "y"), ('φ', "f"), ('χ', "ch"), ('ψ', "ps"), ('ω', "o"),
+    ('ϊ', "i"), ('ϋ', "y"), ('ό', "o"), ('ύ', "y"), ('ώ', "o"), ('ϐ', "v"), ('ϑ', "th"), ('ϕ', "f"),
+    ('ϖ', "p"), ('ϰ', "k"), ('ϱ', "r"), ('ϲ', "s"), ('ϳ', "j"), ('ϵ', "e"), ('а', "a"), ('б', "b"),
+    ('в', "v"), ('г', "g"), ('д', "d"), ('е', "e"), ('ж', "zh"), ('з', "z"), ('и', "i"), ('й', "y"),
+    ('к', "k"), ('л', "l"), ('м', "m"), ('н', "n"), ('о', "o"), ('п', "p"), ('р', "r"), ('с', "s"),
+    ('т', "t"), ('у', "u"), ('ф', "f"), ('х', "kh"), ('ц', "ts"), ('ч', "ch"), ('ш', "sh"),
+    ('щ', "shch"), ('ъ', ""), ('ы', "y"), ('ь', ""), ('э', "e"), ('ю', "yu"), ('я', "ya"),
+    ('ѐ', "e"), ('ё', "e"), ('ђ', "dj"), ('ѓ', "gj"), ('є', "ye"), ('ѕ', "dz"), ('і', "i"),
+    ('ї', "yi"), ('ј', "j"), ('љ', "lj"), ('њ', "nj"), ('ћ', "c"), ('ќ', "kj"), ('ѝ', "i"),
+    ('ў', "u"), ('џ', "dz"), ('ґ', "g"), ('ғ', "gh"), ('қ', "q"), ('ң', "ng"), ('ү', "u"),
+    ('ұ', "u"), ('һ', "h"), ('ӂ', "zh"), ('ӑ', "a"), ('ӓ', "a"), ('ӗ', "e"), ('ә', "a"), ('ӛ', "a"),
+    ('ӝ', "zh"), ('ӟ', "z"), ('ӣ', "i"), ('ӥ', "i"), ('ӧ', "o"), ('ө', "o"), ('ӫ', "o"), ('ӭ', "e"),
+    ('ӯ', "u"), ('ӱ', "u"), ('ӳ', "u"), ('ӵ', "ch"), ('ӹ', "y"), ('ḁ', "a"), ('ḃ', "b"), ('ḅ', "b"),
+    ('ḇ', "b"), ('ḉ', "c"), ('ḋ', "d"), ('ḍ', "d"), ('ḏ', "d"), ('ḑ', "d"), ('ḓ', "d"), ('ḕ', "e"),
+    ('ḗ', "e"), ('ḙ', "e"), ('ḛ', "e"), ('ḝ', "e"), ('ḟ', "f"), ('ḡ', "g"), ('ḣ', "h"), ('ḥ', "h"),
+    ('ḧ', "h"), ('ḩ', "h"), ('ḫ', "h"), ('ḭ', "i"), ('ḯ', "i"), ('ḱ', "k"), ('ḳ', "k"), ('ḵ', "k"),
+    ('ḷ', "l"), ('ḹ', "l"), ('ḻ', "l"), ('ḽ', "l"), ('ḿ', "m"), ('ṁ', "m"), ('ṃ', "m"), ('ṅ', "n"),
+    ('ṇ', "n"), ('ṉ', "n"), ('ṋ', "n"), ('ṍ', "o"), ('ṏ', "o"), ('ṑ', "o"), ('ṓ', "o"), ('ṕ', "p"),
+    ('ṗ', "p"), ('ṙ', "r"), ('ṛ', "r"), ('ṝ', "r"), ('ṟ', "r"), ('ṡ', "s"), ('ṣ', "s"), ('ṥ', "s"),
+    ('ṧ', "s"), ('ṩ', "s"), ('ṫ', "t"), ('ṭ', "t"), ('ṯ', "t"), ('ṱ', "t"), ('ṳ', "u"), ('ṵ', "u"),
+    ('ṷ', "u"), ('ṹ', "u"), ('ṻ', "u"), ('ṽ', "v"), ('ṿ', "v"), ('ẁ', "w"), ('ẃ', "w"), ('ẅ', "w"),
+    ('ẇ', "w"), ('ẉ', "w"), ('ẋ', "x"), ('ẍ', "x"), ('ẏ', "y"), ('ẑ', "z"), ('ẓ', "z"), ('ẕ', "z"),
+    ('ẖ', "h"), ('ẗ', "t"), ('ẘ', "w"), ('ẙ', "y"), ('ẛ', "s"), ('ẜ', "s"), ('ẝ', "s"), ('ẟ', "d"),
+    ('ạ', "a"), ('ả', "a"), ('ấ', "a"), ('ầ', "a"), ('ẩ', "a"), ('ẫ', "a"), ('ậ', "a"), ('ắ', "a"),
+    ('ằ', "a"), ('ẳ', "a"), ('ẵ', "a"), ('ặ', "a"), ('ẹ', "e"), ('ẻ', "e"), ('ẽ', "e"), ('ế', "e"),
+    ('ề', "e"), ('ể', "e"), ('ễ', "e"), ('ệ', "e"), ('ỉ', "i"), ('ị', "i"), ('ọ', "o"), ('ỏ', "o"),
+    ('ố', "o"), ('ồ', "o"), ('ổ', "o"), ('ỗ', "o"), ('ộ', "o"), ('ớ', "o"), ('ờ', "o"), ('ở', "o"),
+    ('ỡ', "o"), ('ợ', "o"), ('ụ', "u"), ('ủ', "u"), ('ứ', "u"), ('ừ', "u"), ('ử', "u"), ('ữ', "u"),
+    ('ự', "u"), ('ỳ', "y"), ('ỵ', "y"), ('ỷ', "y"), ('ỹ', "y"), ('ỻ', "ll"), ('ỽ', "v"), ('ỿ', "y"),
+    ('ἀ', "a"), ('ἁ', "a"), ('ἂ', "a"), ('ἃ', "a"), ('ἄ', "a"), ('ἅ', "a"), ('ἆ', "a"), ('ἇ', "a"),
+    ('ἐ', "e"), ('ἑ', "e"), ('ἒ', "e"), ('ἓ', "e"), ('ἔ', "e"), ('ἕ', "e"), ('ἠ', "i"), ('ἡ', "i"),
+    ('ἢ', "i"), ('ἣ', "i"), ('ἤ', "i"), ('ἥ', "i"), ('ἦ', "i"), ('ἧ', "i"), ('ἰ', "i"), ('ἱ', "i"),
+    ('ἲ', "i"), ('ἳ', "i"), ('ἴ', "i"), ('ἵ', "i"), ('ἶ', "i"), ('ἷ', "i"), ('ὀ', "o"), ('ὁ', "o"),
+    ('ὂ', "o"), ('ὃ', "o"), ('ὄ', "o"), ('ὅ', "o"), ('ὐ', "y"), ('ὑ', "y"), ('ὒ', "y"), ('ὓ', "y"),
+    ('ὔ', "y"), ('ὕ', "y"), ('ὖ', "y"), ('ὗ', "y"), ('ὠ', "o"), ('ὡ', "o"), ('ὢ', "o"), ('ὣ', "o"),
+    ('ὤ', "o"), ('ὥ', "o"), ('ὦ', "o"), ('ὧ', "o"), ('ὰ', "a"), ('ά', "a"), ('ὲ', "e"), ('έ', "e"),
+    ('ὴ', "i"), ('ή', "i"), ('ὶ', "i"), ('ί', "i"), ('ὸ', "o"), ('ό', "o"), ('ὺ', "y"), ('ύ', "y"),
+    ('ὼ', "o"), ('ώ', "o"), ('ᾀ', "a"), ('ᾁ', "a"), ('ᾂ', "a"), ('ᾃ', "a"), ('ᾄ', "a"), ('ᾅ', "a"),
+    ('ᾆ', "a"), ('ᾇ', "a"), ('ᾐ', "i"), ('ᾑ', "i"), ('ᾒ', "i"), ('ᾓ', "i"), ('ᾔ', "i"), ('ᾕ', "i"),
+    ('ᾖ', "i"), ('ᾗ', "i"), ('ᾠ', "o"), ('ᾡ', "o"), ('ᾢ', "o"), ('ᾣ', "o"), ('ᾤ', "o"), ('ᾥ', "o"),
+    ('ᾦ', "o"), ('ᾧ', "o"), ('ᾰ', "a"), ('ᾱ', "a"), ('ᾲ', "a"), ('ᾳ', "a"), ('ᾴ', "a"), ('ᾶ', "a"),
+    ('ᾷ', "a"), ('ι', "i"), ('ῂ', "i"), ('ῃ', "i"), ('ῄ', "i"), ('ῆ', "i"), ('ῇ', "i"), ('ῐ', "i"),
+    ('ῑ', "i"), ('ῒ', "i"), ('ΐ', "i"), ('ῖ', "i"), ('ῗ', "i"), ('ῠ', "y"), ('ῡ', "y"), ('ῢ', "y"),
+    ('ΰ', "y"), ('ῤ', "r"), ('ῥ', "r"), ('ῦ', "y"), ('ῧ', "y"), ('ῲ', "o"), ('ῳ', "o"), ('ῴ', "o"),
+    ('ῶ', "o"), ('ῷ', "o"), ('ⱥ', "a"), ('ⱦ', "t"),
+];
+
+// None se il carattere non è nella tabella
+pub fn translit(c: char) -> Option<&'static str> {
+    TABLE.binary_search_by_key(&c, |&(k, _)| k).ok().map(|i| TABLE[i].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tabella_ordinata() {
+        assert!(TABLE.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_tabella_solo_ascii() {
+        assert!(TABLE.iter().all(|(_, s)| s.chars().all(|c| c.is_ascii_lowercase())));
+    }
+}
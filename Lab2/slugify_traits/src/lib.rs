@@ -0,0 +1,287 @@
+
+const SUBS_I: &str = "àáâäæãåāăąçćčđďèéêëēėęěğǵḧîïíīįìıİłḿñńǹňôöòóœøōõőṕŕřßśšşșťțûüùúūǘůűųẃẍÿýžźż";
+const SUBS_O: &str = "aaaaaaaaaacccddeeeeeeeegghiiiiiiiilmnnnnoooooooooprrsssssttuuuuuuuuuwxyyzzz";
+
+use std::borrow::Cow;
+use std::fmt::Write;
+
+mod locale;
+mod registry;
+mod stream;
+pub use locale::Locale;
+pub use registry::SlugRegistry;
+pub use stream::{slugify_fmt, slugify_io, SlugWriter};
+
+pub trait MySlug {
+    fn is_slug(&self) -> bool;
+    fn to_slug(&self) -> String;
+    // Cow::Borrowed se la stringa è già uno slug, senza allocare
+    fn to_slug_cow(&self) -> Cow<'_, str>;
+    // le regole della lingua hanno la precedenza su SUBS_I/SUBS_O
+    fn to_slug_locale(&self, locale: Locale) -> String;
+}
+
+/*
+//Punto 1
+impl MySlug for String {
+    fn is_slug(&self) -> bool {
+        *self==slugify(self)
+    }
+
+    fn to_slug(&self) -> String {
+        slugify(self)
+    }
+
+}
+
+
+impl MySlug for &str {
+    fn is_slug(&self) -> bool {
+        if *self==slugify(self){
+            true
+        } else { false }
+    }
+
+    fn to_slug(&self) -> String {
+        slugify(self)
+    }
+
+}
+*/
+
+//Punto 2
+impl<T> MySlug for T
+    where
+        T:?Sized, //Superfluo
+        T: AsRef<str> {
+    // equivale a *self.as_ref()==slugify(self.as_ref()), ma in una passata e senza allocare:
+    // solo [a-z0-9-], niente "--" e niente '-' finale (a meno che sia tutta la stringa)
+    fn is_slug(&self) -> bool {
+        let s = self.as_ref().as_bytes();
+        let mut prev = 0;
+        for &c in s {
+            match c {
+                b'a'..=b'z' | b'0'..=b'9' => {}
+                b'-' if prev != b'-' => {}
+                _ => return false,
+            }
+            prev = c;
+        }
+        s.len() <= 1 || prev != b'-'
+    }
+
+    fn to_slug(&self) -> String {
+        slugify(self.as_ref())
+    }
+
+    fn to_slug_cow(&self) -> Cow<'_, str> {
+        if self.is_slug() {
+            Cow::Borrowed(self.as_ref())
+        } else {
+            Cow::Owned(self.to_slug())
+        }
+    }
+
+    fn to_slug_locale(&self, locale: Locale) -> String {
+        slugify_locale(self.as_ref(), locale)
+    }
+}
+
+
+pub fn slugify(s: &str) -> String {
+    slugify_locale(s, Locale::Default)
+}
+
+pub fn slugify_locale(s: &str, locale: Locale) -> String {
+    let mut w = SlugWriter::with_locale(String::new(), locale);
+    // scrivere su una String non fallisce
+    w.write_str(s).unwrap();
+    w.finish().unwrap()
+}
+
+// SUBS_O è ASCII, quindi l'i-esimo carattere è anche l'i-esimo byte
+fn conv(c: char) -> &'static str {
+    match SUBS_I.chars().position(|x| x == c) {
+        Some(index) => SUBS_O.get(index..index + 1).unwrap_or("-"), // Se il carattere non è in SUBS_O, restituisce "-" altrimenti il carattere corretto
+        None => "-" // Se il carattere non è in SUBS_I, restituisce "-"
+    }
+}
+
+// Sezione per gli unit test
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    #[test]
+    fn test_conversione_lettera_accentata() {
+        let carattere_accentato = "é";
+        let carattere_convertito = slugify(carattere_accentato);
+        assert_eq!(carattere_convertito, "e");
+    }
+
+    #[test]
+    fn test_conversione_lettera_non_accentata() {
+        let carattere_non_accentato = "a";
+        let carattere_convertito = slugify(carattere_non_accentato);
+        assert_eq!(carattere_convertito, "a");
+    }
+
+    #[test]
+    fn test_conversione_carattere_non_amesso() {
+        let carattere_non_amesso = "#";
+        let carattere_convertito = slugify(carattere_non_amesso);
+        assert_eq!(carattere_convertito, "-");
+    }
+
+    #[test]
+    fn test_conversione_carattere_accentato_non_compreso() {
+        let carattere_accentato_non_compreso = "ῶ";
+        let carattere_convertito = slugify(carattere_accentato_non_compreso);
+        assert_eq!(carattere_convertito, "-");
+    }
+
+    #[test]
+    fn test_slugify_stringa_con_spazi() {
+        let stringa = "Ciao mondo";
+        let stringa_slugify = slugify(stringa);
+        assert_eq!(stringa_slugify, "ciao-mondo");
+    }
+
+    #[test]
+    fn test_slugify_stringa_caratteri_accentati() {
+        let stringa = "àéè";
+        let stringa_slugify = slugify(stringa);
+        assert_eq!(stringa_slugify, "aee");
+    }
+
+    #[test]
+    fn test_slugify_stringa_con_caratteri_non_validi() {
+        let stringa = "C#a#o";
+        let stringa_slugify = slugify(stringa);
+        assert_eq!(stringa_slugify, "c-a-o");
+    }
+
+    #[test]
+    fn test_slugify_stringa_con_solo_caratteri_non_validi() {
+        let stringa = "###";
+        let stringa_slugify = slugify(stringa);
+        assert_eq!(stringa_slugify, "-");
+    }
+
+    #[test]
+    fn test_slugify_stringa_con_spazio_alla_fine() {
+        let stringa = "Ciao ";
+        let stringa_slugify = slugify(stringa);
+        assert_eq!(stringa_slugify, "ciao");
+    }
+
+    #[test]
+    fn test_slugify_stringa_con_caratteri_non_validi_consecutivi_alla_fine() {
+        let stringa = "Ciao##";
+        let stringa_slugify = slugify(stringa);
+        assert_eq!(stringa_slugify, "ciao");
+    }
+
+    #[test]
+    fn test_locale_tedesco() {
+        assert_eq!("Grüße aus Köln".to_slug_locale(Locale::De), "gruesse-aus-koeln");
+        assert_eq!(String::from("Müller").to_slug_locale(Locale::De), "mueller");
+    }
+
+    #[test]
+    fn test_locale_svedese() {
+        assert_eq!("Ångström".to_slug_locale(Locale::Sv), "aangstroem");
+    }
+
+    #[test]
+    fn test_locale_default_uguale_a_to_slug() {
+        assert_eq!("Müller Ångström".to_slug_locale(Locale::Default), "Müller Ångström".to_slug());
+        assert_eq!("Müller Ångström".to_slug(), "muller-angstrom");
+    }
+
+    #[test]
+    fn test_locale_da_stringa() {
+        assert_eq!("sv".parse::<Locale>(), Ok(Locale::Sv));
+        assert!("xx".parse::<Locale>().is_err());
+    }
+
+    #[test]
+    fn test_is_slug_uguale_al_confronto_con_slugify() {
+        let casi = [
+            "", "-", "--", "a", "a-", "-a", "a-b", "a--b", "ciao-mondo", "Ciao", "ciao mondo", "ciao_mondo",
+            "àéè", "aee", "c-a-o", "ciao-", "123-abc", "#", "İ", "ß",
+        ];
+        for s in casi {
+            assert_eq!(s.is_slug(), s == slugify(s), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_to_slug_cow() {
+        assert!(matches!("ciao-mondo".to_slug_cow(), Cow::Borrowed("ciao-mondo")));
+        let s = String::from("Ciao mondo");
+        let cow = s.to_slug_cow();
+        assert!(matches!(cow, Cow::Owned(_)));
+        assert_eq!(cow, "ciao-mondo");
+    }
+
+    #[test]
+    fn test_slug_writer_a_pezzi() {
+        let mut w = SlugWriter::new(String::new());
+        for pezzo in ["Ciao ", "", " mon", "do", "!!", " àé", "è##"] {
+            w.write_str(pezzo).unwrap();
+        }
+        assert_eq!(w.finish().unwrap(), slugify("Ciao  mondo!! àéè##"));
+    }
+
+    #[test]
+    fn test_slug_writer_uguale_a_slugify() {
+        for s in ["", "#", "###", "#ciao", "Ciao##", "C#a#o", "àéè", "Ciao mondo", "İ"] {
+            let mut out = String::new();
+            slugify_fmt(s, &mut out).unwrap();
+            assert_eq!(out, slugify(s), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_slugify_io() {
+        let mut out = vec![];
+        slugify_io("Ciao mondo", &mut out).unwrap();
+        assert_eq!(out, b"ciao-mondo");
+    }
+
+    #[test]
+    fn test_slugify_io_bufferizzato() {
+        struct Conta(usize, Vec<u8>);
+        impl io::Write for Conta {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0 += 1;
+                self.1.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        let testo = "Capitolo primo, ".repeat(100);
+        let mut out = Conta(0, vec![]);
+        slugify_io(&testo, &mut out).unwrap();
+        assert_eq!(out.0, 1);
+        assert_eq!(String::from_utf8(out.1).unwrap(), slugify(&testo));
+    }
+
+    #[test]
+    fn test_slugify_io_errore() {
+        struct Pieno;
+        impl io::Write for Pieno {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::StorageFull, "pieno"))
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        assert_eq!(slugify_io("ciao", Pieno).unwrap_err().kind(), io::ErrorKind::StorageFull);
+    }
+}
@@ -0,0 +1,42 @@
+// Regole di traslitterazione per lingua, controllate prima di SUBS_I/SUBS_O:
+// sono elencati solo i caratteri che una lingua scrive in modo diverso.
+
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    Default,
+    // tedesco
+    De,
+    // svedese
+    Sv,
+}
+
+const DE: &[(char, &str)] = &[('ß', "ss"), ('ä', "ae"), ('ö', "oe"), ('ü', "ue")];
+const SV: &[(char, &str)] = &[('ä', "ae"), ('å', "aa"), ('ö', "oe")];
+
+impl Locale {
+    // None se la lingua non ridefinisce il carattere
+    pub fn translit(self, c: char) -> Option<&'static str> {
+        let table = match self {
+            Locale::Default => &[][..],
+            Locale::De => DE,
+            Locale::Sv => SV,
+        };
+        table.iter().find(|&&(k, _)| k == c).map(|&(_, s)| s)
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "default" => Ok(Locale::Default),
+            "de" => Ok(Locale::De),
+            "sv" => Ok(Locale::Sv),
+            _ => Err(format!("unknown locale '{}'", s)),
+        }
+    }
+}
@@ -1,85 +1,8 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::io;
 
-const SUBS_I: &str = "àáâäæãåāăąçćčđďèéêëēėęěğǵḧîïíīįìıİłḿñńǹňôöòóœøōõőṕŕřßśšşșťțûüùúūǘůűųẃẍÿýžźż";
-const SUBS_O: &str = "aaaaaaaaaacccddeeeeeeeegghiiiiiiiilmnnnnoooooooooprrsssssttuuuuuuuuuwxyyzzz";
-
-trait MySlug {
-    fn is_slug(&self) -> bool;
-    fn to_slug(&self) -> String;
-}
-
-/*
-//Punto 1
-impl MySlug for String {
-    fn is_slug(&self) -> bool {
-        *self==slugify(self)
-    }
-
-    fn to_slug(&self) -> String {
-        slugify(self)
-    }
-
-}
-
-
-impl MySlug for &str {
-    fn is_slug(&self) -> bool {
-        if *self==slugify(self){
-            true
-        } else { false }
-    }
-
-    fn to_slug(&self) -> String {
-        slugify(self)
-    }
-
-}
-*/
-
-//Punto 2
-impl<T> MySlug for T
-    where
-        T:?Sized, //Superfluo
-        T: AsRef<str> {
-    fn is_slug(&self) -> bool {
-        if *self.as_ref()==slugify(self.as_ref()){
-            true
-        } else { false }
-    }
-
-    fn to_slug(&self) -> String {
-        slugify(self.as_ref())
-    }
-}
-
-
-fn slugify(s: &str) -> String {
-    let mut result = String::new();
-    for c in s.to_lowercase().chars(){
-        if c.is_ascii_alphanumeric(){
-            result.push(c);
-        } else {
-            let cc = conv(c);
-            if cc == '-' {
-                if !result.ends_with('-') {
-                    result.push(cc);
-                }
-            } else {
-                result.push(cc);
-            }
-        }
-    }
-    if result.len()>1 && result.ends_with('-'){
-        result.pop();
-    }
-    result
-}
-
-fn conv(c: char) -> char {
-    match SUBS_I.chars().position(|x| x == c) {
-        Some(index) => SUBS_O.chars().nth(index).unwrap_or('-'), // Se il carattere non è in SUBS_O, restituisce "-" altrimenti il carattere corretto
-        None => '-' // Se il carattere non è in SUBS_I, restituisce "-"
-    }
-}
+use slugify_traits::{slugify_fmt, slugify_io, Locale, MySlug, SlugRegistry, SlugWriter};
 
 fn main() {
 
@@ -91,82 +14,27 @@ fn main() {
     let s3: String = s1.to_slug();
     let s4: String = s2.to_slug();
     println!("s3:{} s4:{}", s3, s4); // stampa:  s3:hello-string s4:hello-slice
-}
-
-// Sezione per gli unit test
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[test]
-    fn test_conversione_lettera_accentata() {
-        let carattere_accentato = "é";
-        let carattere_convertito = slugify(carattere_accentato);
-        assert_eq!(carattere_convertito, "e");
-    }
-
-    #[test]
-    fn test_conversione_lettera_non_accentata() {
-        let carattere_non_accentato = "a";
-        let carattere_convertito = slugify(carattere_non_accentato);
-        assert_eq!(carattere_convertito, "a");
-    }
+    let locale: Locale = "de".parse().unwrap();
+    println!("{}", "Grüße aus Köln".to_slug_locale(locale)); // gruesse-aus-koeln
 
-    #[test]
-    fn test_conversione_carattere_non_amesso() {
-        let carattere_non_amesso = "#";
-        let carattere_convertito = slugify(carattere_non_amesso);
-        assert_eq!(carattere_convertito, "-");
-    }
-
-    #[test]
-    fn test_conversione_carattere_accentato_non_compreso() {
-        let carattere_accentato_non_compreso = "ῶ";
-        let carattere_convertito = slugify(carattere_accentato_non_compreso);
-        assert_eq!(carattere_convertito, "-");
-    }
+    println!("{}", matches!(s2.to_slug_cow(), Cow::Borrowed(_))); // true, nessuna copia
 
-    #[test]
-    fn test_slugify_stringa_con_spazi() {
-        let stringa = "Ciao mondo";
-        let stringa_slugify = slugify(stringa);
-        assert_eq!(stringa_slugify, "ciao-mondo");
+    // lo slug di un testo lungo scritto direttamente sul sink, un pezzo alla volta
+    let mut w = SlugWriter::new(String::new());
+    for chapter in 1..=3 {
+        write!(w, "Capitolo {}, ", chapter).unwrap();
     }
+    println!("{}", w.finish().unwrap()); // capitolo-1-capitolo-2-capitolo-3
+    let mut line = String::new();
+    slugify_fmt("Ciao mondo", &mut line).unwrap();
+    println!("{}", line); // ciao-mondo
+    slugify_io("Ciao mondo", io::stdout()).unwrap(); // ciao-mondo
+    println!();
 
-    #[test]
-    fn test_slugify_stringa_caratteri_accentati() {
-        let stringa = "àéè";
-        let stringa_slugify = slugify(stringa);
-        assert_eq!(stringa_slugify, "aee");
-    }
-
-    #[test]
-    fn test_slugify_stringa_con_caratteri_non_validi() {
-        let stringa = "C#a#o";
-        let stringa_slugify = slugify(stringa);
-        assert_eq!(stringa_slugify, "c-a-o");
-    }
-
-    #[test]
-    fn test_slugify_stringa_con_solo_caratteri_non_validi() {
-        let stringa = "###";
-        let stringa_slugify = slugify(stringa);
-        assert_eq!(stringa_slugify, "-");
-    }
-
-    #[test]
-    fn test_slugify_stringa_con_spazio_alla_fine() {
-        let stringa = "Ciao ";
-        let stringa_slugify = slugify(stringa);
-        assert_eq!(stringa_slugify, "ciao");
-    }
-
-    #[test]
-    fn test_slugify_stringa_con_caratteri_non_validi_consecutivi_alla_fine() {
-        let stringa = "Ciao##";
-        let stringa_slugify = slugify(stringa);
-        assert_eq!(stringa_slugify, "ciao");
-    }
-
-
+    let registry = SlugRegistry::new();
+    println!("{}", registry.register("Ciao mondo")); // ciao-mondo
+    println!("{}", registry.register("Ciao mondo")); // ciao-mondo-2
+    registry.release("ciao-mondo");
+    println!("{}", registry.contains("ciao-mondo")); // false
 }
@@ -0,0 +1,172 @@
+// Registro degli slug già assegnati: due titoli uguali ricevono slug diversi
+// ("ciao-mondo", "ciao-mondo-2", ...). Lo stato è protetto da un Mutex,
+// quindi il registro si può condividere tra thread con un Arc; processi diversi invece non
+// vedono i rispettivi slug, e con save l'ultimo che salva vince.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::MySlug;
+
+// la base degli slug dei titoli senza lettere né cifre ("", "###")
+const UNTITLED: &str = "untitled";
+
+#[derive(Debug, Default)]
+pub struct SlugRegistry {
+    slugs: Mutex<HashSet<String>>,
+}
+
+impl SlugRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // riserva e restituisce il primo slug libero per il titolo
+    pub fn register(&self, title: &str) -> String {
+        let mut base = title.to_slug();
+        // "" e "-" non sono slug validi, e nemmeno "-2" o "--2"
+        if base.is_empty() || base == "-" {
+            base = UNTITLED.to_string();
+        }
+        let mut slugs = self.slugs.lock().unwrap();
+        let mut slug = base.clone();
+        let mut n = 2;
+        while slugs.contains(&slug) {
+            slug = format!("{}-{}", base, n);
+            n += 1;
+        }
+        slugs.insert(slug.clone());
+        slug
+    }
+
+    // false se lo slug non era registrato
+    pub fn release(&self, slug: &str) -> bool {
+        self.slugs.lock().unwrap().remove(slug)
+    }
+
+    pub fn contains(&self, slug: &str) -> bool {
+        self.slugs.lock().unwrap().contains(slug)
+    }
+
+    // un file di testo con uno slug per riga
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let slugs = text.lines().map(str::trim).filter(|l| !l.is_empty()).map(String::from).collect();
+        Ok(SlugRegistry { slugs: Mutex::new(slugs) })
+    }
+
+    // scrive un file temporaneo e poi lo rinomina, così chi legge non vede mai un file a metà;
+    // il file temporaneo è "<path>.<pid>.tmp", diverso per ogni file e per ogni processo
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut slugs: Vec<String> = self.slugs.lock().unwrap().iter().cloned().collect();
+        slugs.sort();
+        let mut text = slugs.join("\n");
+        text.push('\n');
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(format!(".{}.tmp", std::process::id()));
+        fs::write(&tmp, text)?;
+        fs::rename(&tmp, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_titoli_uguali_slug_diversi() {
+        let reg = SlugRegistry::new();
+        assert_eq!(reg.register("Ciao mondo"), "ciao-mondo");
+        assert_eq!(reg.register("Ciao mondo"), "ciao-mondo-2");
+        assert_eq!(reg.register("Ciao, mondo!"), "ciao-mondo-3");
+    }
+
+    #[test]
+    fn test_suffisso_gia_usato_da_un_titolo() {
+        let reg = SlugRegistry::new();
+        assert_eq!(reg.register("Ciao mondo 2"), "ciao-mondo-2");
+        assert_eq!(reg.register("Ciao mondo"), "ciao-mondo");
+        assert_eq!(reg.register("Ciao mondo"), "ciao-mondo-3");
+    }
+
+    #[test]
+    fn test_rilascio() {
+        let reg = SlugRegistry::new();
+        reg.register("Ciao mondo");
+        reg.register("Ciao mondo");
+        assert!(reg.release("ciao-mondo"));
+        assert!(!reg.release("ciao-mondo"));
+        assert!(!reg.contains("ciao-mondo"));
+        assert_eq!(reg.register("Ciao mondo"), "ciao-mondo");
+        assert_eq!(reg.register("Ciao mondo"), "ciao-mondo-3");
+    }
+
+    #[test]
+    fn test_thread_in_parallelo() {
+        let reg = Arc::new(SlugRegistry::new());
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let reg = Arc::clone(&reg);
+                thread::spawn(move || (0..50).map(|_| reg.register("Ciao mondo")).collect::<Vec<_>>())
+            })
+            .collect();
+        let all: Vec<String> = handles.into_iter().flat_map(|h| h.join().unwrap()).collect();
+        let unique: HashSet<&String> = all.iter().collect();
+        assert_eq!(all.len(), 400);
+        assert_eq!(unique.len(), 400);
+        assert!(reg.contains("ciao-mondo") && reg.contains("ciao-mondo-400"));
+    }
+
+    #[test]
+    fn test_salva_e_carica() {
+        let path = std::env::temp_dir().join(format!("slug_registry_{}.txt", std::process::id()));
+        let reg = SlugRegistry::new();
+        reg.register("Ciao mondo");
+        reg.register("Ciao mondo");
+        reg.register("Hello");
+        reg.save(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "ciao-mondo\nciao-mondo-2\nhello\n");
+
+        let loaded = SlugRegistry::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(loaded.contains("ciao-mondo-2"));
+        assert_eq!(loaded.register("Ciao mondo"), "ciao-mondo-3");
+    }
+
+    #[test]
+    fn test_titoli_senza_lettere() {
+        let reg = SlugRegistry::new();
+        assert_eq!(reg.register("###"), "untitled");
+        assert_eq!(reg.register(""), "untitled-2");
+        assert_eq!(reg.register("!!"), "untitled-3");
+        assert!(["untitled", "untitled-2", "untitled-3"].iter().all(|s| s.is_slug()));
+    }
+
+    #[test]
+    fn test_salva_non_tocca_altri_file() {
+        let dir = std::env::temp_dir();
+        let name = format!("slug_registry_tmp_{}", std::process::id());
+        // con with_extension("tmp") sarebbero stati lo stesso file temporaneo
+        let other = dir.join(format!("{}.tmp", name));
+        fs::write(&other, "altro").unwrap();
+        let reg = SlugRegistry::new();
+        reg.register("Ciao");
+        reg.save(dir.join(format!("{}.json", name))).unwrap();
+        reg.save(dir.join(format!("{}.txt", name))).unwrap();
+        assert_eq!(fs::read_to_string(&other).unwrap(), "altro");
+        for ext in ["tmp", "json", "txt"] {
+            fs::remove_file(dir.join(format!("{}.{}", name, ext))).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_carica_file_mancante() {
+        assert!(SlugRegistry::load("/non/esiste/slugs.txt").is_err());
+    }
+}
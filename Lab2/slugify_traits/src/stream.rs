@@ -0,0 +1,112 @@
+// Slugify incrementale: il testo arriva a pezzi con write_str e lo slug esce subito sul sink,
+// senza tenere in memoria né il testo né lo slug. Serve solo ricordare se c'è un '-' in sospeso:
+// viene scritto quando arriva il carattere successivo, così i '-' consecutivi diventano uno
+// e quello finale sparisce.
+
+use std::fmt::{self, Write};
+use std::io::{self, BufWriter, Write as _};
+
+use crate::conv;
+use crate::locale::Locale;
+
+pub struct SlugWriter<W> {
+    out: W,
+    locale: Locale,
+    // è già stato scritto qualcosa
+    written: bool,
+    dash: bool,
+}
+
+impl<W: fmt::Write> SlugWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self::with_locale(out, Locale::Default)
+    }
+
+    pub fn with_locale(out: W, locale: Locale) -> Self {
+        SlugWriter { out, locale, written: false, dash: false }
+    }
+
+    fn push(&mut self, s: &str) -> fmt::Result {
+        if s == "-" {
+            self.dash = true;
+            return Ok(());
+        }
+        if self.dash {
+            self.out.write_char('-')?;
+            self.dash = false;
+        }
+        self.written = true;
+        self.out.write_str(s)
+    }
+
+    // un testo fatto solo di separatori dà "-", come slugify
+    fn end(&mut self) -> fmt::Result {
+        if self.dash && !self.written {
+            self.out.write_char('-')?;
+        }
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<W, fmt::Error> {
+        self.end()?;
+        Ok(self.out)
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for SlugWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut buf = [0; 4];
+        for c in s.chars().flat_map(char::to_lowercase) {
+            if c.is_ascii_alphanumeric() {
+                self.push(c.encode_utf8(&mut buf))?;
+            } else {
+                self.push(self.locale.translit(c).unwrap_or_else(|| conv(c)))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// adatta un io::Write a fmt::Write, tenendo da parte l'errore di I/O
+pub struct IoSink<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> IoSink<W> {
+    pub fn new(inner: W) -> Self {
+        IoSink { inner, error: None }
+    }
+
+    // l'errore di I/O che ha interrotto la scrittura, se c'è
+    pub fn into_result(self) -> io::Result<W> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(self.inner),
+        }
+    }
+}
+
+impl<W: io::Write> fmt::Write for IoSink<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+pub fn slugify_fmt<W: fmt::Write>(s: &str, out: W) -> fmt::Result {
+    let mut w = SlugWriter::new(out);
+    w.write_str(s)?;
+    w.finish().map(|_| ())
+}
+
+// i pezzi dello slug sono piccoli, quindi passano da un BufWriter: una scrittura sul sink ogni
+// 8 KiB invece di una (una syscall, per un file o stdout) per pezzo
+pub fn slugify_io<W: io::Write>(s: &str, out: W) -> io::Result<()> {
+    let mut w = SlugWriter::new(IoSink::new(BufWriter::new(out)));
+    // IoSink fallisce solo per un errore di I/O, che into_result restituisce
+    let _ = w.write_str(s).and_then(|_| w.end());
+    w.out.into_result()?.flush()
+}